@@ -0,0 +1,300 @@
+## Main window
+
+save = Save
+enable = Enable
+add-point = + Point
+yes = yes
+no = no
+primary = Primary
+move-left = < Move
+move-right = Move >
+brightness = Brightness
+input = Input
+image-and-color = Image and color
+schedule = Schedule
+brightness-group = Brightness group
+power = Power
+power-on = On
+power-standby = Standby
+power-off = Off
+power-off-others = Turn off others
+auto-adjust = Snap edges
+reset-vcp = Reset VCP
+details = Details
+nickname = Nickname
+physical-all = All physical monitors
+language = Language
+language-auto = Automatic
+
+## Details (EDID)
+
+details-model = Model
+details-manufacturer = Manufacturer
+details-manufacturer-value = { $manufacturer } (product { $product })
+details-serial = Serial
+details-manufactured = Manufactured
+details-week-of-year = week { $week } of { $year }
+details-extensions =
+    { $count ->
+        [one] { $count } extension
+       *[other] { $count } extensions
+    }
+details-checksum-invalid = invalid checksum
+details-input = Input
+details-analog = Analog
+details-digital = Digital
+details-size = Size
+details-native-mode = Native mode
+details-limits = Limits
+details-gamma = Gamma
+details-chromaticity = Chromaticity
+details-colorimetry = Colorimetry
+details-hdr-luminance = max { $max } nits, average { $average } nits, min { $min } nits
+details-native-vics = Native VICs
+details-supported-modes = Supported modes
+
+## Groups and schedule
+
+groups-none = None
+groups-new = + New group
+groups-default-name = Group { $number }
+groups-name = Name
+groups-curve = Curve (group level → VCP value)
+schedule-sun-times = Sunrise { $sunrise } · Sunset { $sunset }
+schedule-fixed = Time
+schedule-sunrise = Sunrise
+schedule-sunset = Sunset
+
+## Profiles, hotkeys and background
+
+profiles = Profiles
+profiles-apply = Apply
+profiles-name = Profile name
+profiles-save-current = Save current
+layouts = Layouts
+layouts-apply = Apply
+layouts-name = Layout name
+layouts-save-current = Save current
+hotkeys = Hotkeys
+hotkeys-step = Step
+hotkeys-add = + Hotkey
+hotkeys-failed = Not registered: { $keys }
+hotkeys-target-focused = Focused window
+hotkeys-target-under-cursor = Under cursor
+hotkeys-target-all = All
+hotkeys-brightness-up = Brightness +
+hotkeys-brightness-down = Brightness −
+hotkeys-input = Input: { $input }
+hotkeys-profile = Profile: { $profile }
+background = Background
+background-start-at-login = Start with Windows in the tray
+background-go-to-tray = Go to tray
+background-services-running = Schedule, hotkeys and the JSON-RPC server keep running in the tray process.
+tray-all-monitors = All monitors
+tray-brightness = Brightness { $percent }%
+tray-profiles = Profiles
+tray-layouts = Layouts
+tray-open = Open window
+tray-quit = Quit
+
+## Inventory
+
+inventory = Inventory
+inventory-export-json = Export JSON
+inventory-export-csv = Export CSV
+inventory-saved = Saved to { $path }
+
+## Running tasks
+
+task-refresh = Reading monitor
+task-overview = Reading monitors
+task-set-resolution = Changing resolution
+task-set-primary = Setting primary monitor
+task-move = Moving monitor
+task-auto-adjust = Snapping edges
+task-reset = Resetting VCP
+task-set-vcp = Applying VCP
+task-set-input = Switching input
+task-set-power = Changing power mode
+task-turn-off-others = Turning off other monitors
+task-export-inventory = Exporting inventory
+task-capture-profile = Saving profile
+task-apply-profile = Applying profile
+task-capture-layout = Saving layout
+task-apply-layout = Applying layout
+
+## VCP names
+
+vcp-contrast = Contrast
+vcp-color-preset = Color preset
+vcp-red-gain = Red gain
+vcp-green-gain = Green gain
+vcp-blue-gain = Blue gain
+vcp-red-black-level = Red black level
+vcp-green-black-level = Green black level
+vcp-blue-black-level = Blue black level
+vcp-sharpness = Sharpness
+vcp-gamma = Gamma
+input-composite-1 = Composite 1
+input-composite-2 = Composite 2
+input-tuner-1 = Tuner 1
+input-tuner-2 = Tuner 2
+input-tuner-3 = Tuner 3
+input-component-1 = Component 1
+input-component-2 = Component 2
+input-component-3 = Component 3
+input-unknown = Input { $code }
+color-preset-native = Native
+color-preset-user-1 = User 1
+color-preset-user-2 = User 2
+color-preset-user-3 = User 3
+color-preset-unknown = Preset { $code }
+connector-internal = Internal
+
+## Errors
+
+error-monitor-index-not-found = Monitor with index { $index } not found
+error-monitor-not-found = Monitor not found
+error-resolution-unsupported = Resolution { $width }x{ $height } not supported on monitor '{ $monitor }'
+error-set-display-config = SetDisplayConfig failed with code { $code }
+error-current-settings = Could not read the current settings of monitor '{ $monitor }'
+error-unknown-power-mode = Unknown power mode { $value }
+error-invalid-capabilities = Invalid capabilities on monitor '{ $monitor }': { $reason }
+error-device-unidentified = Unidentified device on monitor '{ $monitor }'
+error-invalid-edid = Invalid EDID on monitor '{ $monitor }': { $reason }
+error-physical-not-found = Physical monitor { $target } not found on '{ $monitor }'
+error-ipc-server = JSON-RPC server: { $reason }
+error-start-at-login = Start at login: { $reason }
+error-tray = Tray: { $reason }
+capabilities-empty = Empty capabilities string
+capabilities-unexpected-char = Unexpected character '{ $found }' at position { $pos }
+capabilities-missing-value = Key '{ $key }' has no value at position { $pos }
+edid-too-short = EDID has { $len } bytes, expected at least { $min }
+edid-invalid-header = Invalid EDID header
+ddc-write-not-confirmed = VCP value { $code } not confirmed: wrote { $written }, read { $read }
+ddc-code-disabled = VCP code { $code } disabled for model '{ $model }'
+monitor-not-found = Monitor '{ $monitor }' not found
+brightness-out-of-range = Brightness must be between 0 and 100
+invalid-vcp-code = Invalid VCP code '{ $code }'
+rpc-empty-batch = Empty batch
+rpc-invalid-request = Invalid JSON-RPC 2.0 request
+rpc-method-not-found = Method '{ $method }' does not exist
+rpc-missing-monitor = Missing 'monitor' parameter
+rpc-invalid-param = Invalid '{ $key }' parameter
+
+## Command line
+
+cli-physical-requires-number = --physical requires a number
+cli-invalid-physical = Invalid physical monitor '{ $value }'
+cli-unknown-option = Unknown option '{ $option }'
+cli-invalid-mode = Invalid mode '{ $mode }', use WxH (e.g. 1920x1080)
+cli-invalid-number = Invalid number '{ $value }'
+cli-only-requires-list = --only needs a list of categories (factory, luminance, geometry, color)
+cli-invalid-category = Invalid category '{ $value }'; use factory, luminance, geometry or color
+cli-reset-needs-yes = Resetting erases the monitor's adjustments; run again with --yes to confirm
+cli-primary-suffix = primary
+cli-manufacturer = Manufacturer
+cli-model = Model
+cli-serial = Serial
+cli-mode = Mode
+cli-position = Position
+cli-primary = Primary
+cli-vcp-codes = VCP codes
+cli-usage =
+    Usage: DisplayManager <command> [options]
+
+    Commands:
+      list                               List monitors
+      info <monitor>                     Identification, current mode and DDC/CI support
+      set-mode <monitor> <WxH>           Change the resolution (e.g. 1920x1080)
+      move <monitor> <x> <y>             Move the monitor on the desktop
+      set-primary <monitor>              Set the primary monitor
+      brightness get <monitor>           Read brightness in %
+      brightness set <monitor> <0-100>   Change brightness in %
+      vcp get <monitor> <code>           Read a VCP code (hexadecimal, e.g. 10 or 0x10)
+      vcp set <monitor> <code> <value>   Write a VCP code
+      reset <monitor> --yes              Restore factory defaults via VCP
+      inventory [file.json|.csv]         Export the monitor inventory
+
+    Options:
+      --json            JSON output
+      --physical <n>    Physical monitor (starting at 0) for VCP commands
+      --yes             Confirm the reset
+      --only <list>     Reset categories: factory, luminance, geometry, color
+
+    <monitor> accepts the index, the device name (DISPLAY2), the friendly name or the nickname.
+
+    Exit codes: 0 success, 1 failure, 2 usage error, 3 monitor not found, 4 DDC/CI failure
+
+## Settings
+
+settings = Settings
+settings-font-size = Font size
+settings-window-size = Window size
+settings-refresh = Refresh
+settings-refresh-manual = Only on selection
+settings-refresh-after-changes = After changes
+settings-refresh-periodic = Periodic
+settings-refresh-interval = Interval
+settings-confirm-timeout = Confirm resolution within
+settings-invalid-file = Invalid settings in { $path } ({ $reason }); using defaults
+settings-invalid-version = Invalid 'version' field
+settings-newer-version = Settings from version { $version }, newer than supported ({ $supported }); some options may be ignored
+error-save-settings = Could not save settings: { $reason }
+confirm-resolution = Keep resolution?
+confirm-resolution-countdown = Reverting to the previous resolution in { $seconds } s
+confirm-keep = Keep
+confirm-revert = Revert
+
+## Tabs
+
+tab-arrangement = Arrangement
+tab-monitors = Monitors
+tab-vcp = VCP
+tab-profiles = Profiles
+tab-settings = Settings
+position = Position
+apply = Apply
+
+## Notifications
+
+log = Error log
+log-toggle = ⚠ { $count ->
+        [one] 1 error
+       *[other] { $count } errors
+    }
+log-copy = Copy
+log-clear = Clear
+error-brightness = Brightness not applied: { $reason }
+error-vcp-write = VCP { $code } not applied: { $reason }
+warning-monitor-disconnected = Monitor disconnected; the list was refreshed
+done-set-resolution = Resolution changed
+done-set-primary = Primary monitor set
+done-move = Monitor moved
+done-auto-adjust = Edges adjusted
+done-reset = Factory defaults restored
+done-set-input = Input switched
+done-set-power = Power mode changed
+done-turn-off-others = Other monitors turned off
+done-capture-profile = Profile saved
+done-apply-profile = Profile applied
+done-capture-layout = Layout saved
+done-apply-layout = Layout applied
+
+## VCP reset
+
+cancel = Cancel
+reset-intro = Choose what to restore to factory defaults on { $monitor }. Adjustments made in the monitor's menu will be lost.
+reset-factory = Factory defaults
+reset-factory-description = Restores every monitor setting, including those of the other categories.
+reset-luminance-contrast = Brightness and contrast
+reset-luminance-contrast-description = Restores only brightness and contrast.
+reset-geometry = Geometry
+reset-geometry-description = Restores image position and size (relevant for analog inputs).
+reset-color = Color
+reset-color-description = Restores color preset, gains, black levels and gamma.
+reset-backup-note = Current values are copied first; the reset can be undone while the program stays open.
+reset-confirm = Reset
+reset-undo = Undo reset
+task-restore-vcp = Restoring VCP values
+done-restore-vcp = Previous values restored
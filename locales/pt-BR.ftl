@@ -0,0 +1,300 @@
+## Janela principal
+
+save = Salvar
+enable = Ativar
+add-point = + Ponto
+yes = sim
+no = não
+primary = Principal
+move-left = < Mover
+move-right = Mover >
+brightness = Brilho
+input = Entrada
+image-and-color = Imagem e cor
+schedule = Agendamento
+brightness-group = Grupo de brilho
+power = Energia
+power-on = Ligar
+power-standby = Standby
+power-off = Desligar
+power-off-others = Desligar outros
+auto-adjust = Ajustar Bordas
+reset-vcp = Redefinir VCP
+details = Detalhes
+nickname = Apelido
+physical-all = Todos os monitores físicos
+language = Idioma
+language-auto = Automático
+
+## Detalhes (EDID)
+
+details-model = Modelo
+details-manufacturer = Fabricante
+details-manufacturer-value = { $manufacturer } (produto { $product })
+details-serial = Série
+details-manufactured = Fabricação
+details-week-of-year = semana { $week } de { $year }
+details-extensions =
+    { $count ->
+        [one] { $count } extensão
+       *[other] { $count } extensões
+    }
+details-checksum-invalid = checksum inválido
+details-input = Entrada
+details-analog = Analógica
+details-digital = Digital
+details-size = Tamanho
+details-native-mode = Modo nativo
+details-limits = Limites
+details-gamma = Gama
+details-chromaticity = Cromaticidade
+details-colorimetry = Colorimetria
+details-hdr-luminance = máx. { $max } nits, média { $average } nits, mín. { $min } nits
+details-native-vics = VICs nativos
+details-supported-modes = Modos suportados
+
+## Grupos e agendamento
+
+groups-none = Nenhum
+groups-new = + Novo grupo
+groups-default-name = Grupo { $number }
+groups-name = Nome
+groups-curve = Curva (nível do grupo → valor VCP)
+schedule-sun-times = Nascer do sol { $sunrise } · Pôr do sol { $sunset }
+schedule-fixed = Horário
+schedule-sunrise = Nascer do sol
+schedule-sunset = Pôr do sol
+
+## Perfis, atalhos e segundo plano
+
+profiles = Perfis
+profiles-apply = Aplicar
+profiles-name = Nome do perfil
+profiles-save-current = Salvar atual
+layouts = Disposições
+layouts-apply = Aplicar
+layouts-name = Nome da disposição
+layouts-save-current = Salvar atual
+hotkeys = Atalhos
+hotkeys-step = Passo
+hotkeys-add = + Atalho
+hotkeys-failed = Não registrados: { $keys }
+hotkeys-target-focused = Janela em foco
+hotkeys-target-under-cursor = Sob o cursor
+hotkeys-target-all = Todos
+hotkeys-brightness-up = Brilho +
+hotkeys-brightness-down = Brilho −
+hotkeys-input = Entrada: { $input }
+hotkeys-profile = Perfil: { $profile }
+background = Segundo plano
+background-start-at-login = Iniciar com o Windows na bandeja
+background-go-to-tray = Ir para a bandeja
+background-services-running = Agenda, atalhos e servidor JSON-RPC continuam no processo da bandeja.
+tray-all-monitors = Todos os monitores
+tray-brightness = Brilho { $percent }%
+tray-profiles = Perfis
+tray-layouts = Disposições
+tray-open = Abrir janela
+tray-quit = Sair
+
+## Inventário
+
+inventory = Inventário
+inventory-export-json = Exportar JSON
+inventory-export-csv = Exportar CSV
+inventory-saved = Salvo em { $path }
+
+## Tarefas em andamento
+
+task-refresh = Lendo monitor
+task-overview = Lendo monitores
+task-set-resolution = Alterando resolução
+task-set-primary = Definindo monitor principal
+task-move = Movendo monitor
+task-auto-adjust = Ajustando bordas
+task-reset = Redefinindo VCP
+task-set-vcp = Aplicando VCP
+task-set-input = Trocando entrada
+task-set-power = Alterando energia
+task-turn-off-others = Desligando outros monitores
+task-export-inventory = Exportando inventário
+task-capture-profile = Salvando perfil
+task-apply-profile = Aplicando perfil
+task-capture-layout = Salvando disposição
+task-apply-layout = Aplicando disposição
+
+## Nomes de VCP
+
+vcp-contrast = Contraste
+vcp-color-preset = Predefinição de cor
+vcp-red-gain = Ganho vermelho
+vcp-green-gain = Ganho verde
+vcp-blue-gain = Ganho azul
+vcp-red-black-level = Nível de preto vermelho
+vcp-green-black-level = Nível de preto verde
+vcp-blue-black-level = Nível de preto azul
+vcp-sharpness = Nitidez
+vcp-gamma = Gama
+input-composite-1 = Composto 1
+input-composite-2 = Composto 2
+input-tuner-1 = Sintonizador 1
+input-tuner-2 = Sintonizador 2
+input-tuner-3 = Sintonizador 3
+input-component-1 = Componente 1
+input-component-2 = Componente 2
+input-component-3 = Componente 3
+input-unknown = Entrada { $code }
+color-preset-native = Nativo
+color-preset-user-1 = Usuário 1
+color-preset-user-2 = Usuário 2
+color-preset-user-3 = Usuário 3
+color-preset-unknown = Predefinição { $code }
+connector-internal = Interno
+
+## Erros
+
+error-monitor-index-not-found = Monitor com index { $index } não encontrado
+error-monitor-not-found = Monitor não encontrado
+error-resolution-unsupported = Resolução { $width }x{ $height } não suportada no monitor '{ $monitor }'
+error-set-display-config = SetDisplayConfig falhou com código { $code }
+error-current-settings = Não foi possível obter configuração atual do monitor '{ $monitor }'
+error-unknown-power-mode = Modo de energia desconhecido { $value }
+error-invalid-capabilities = Capacidades inválidas no monitor '{ $monitor }': { $reason }
+error-device-unidentified = Dispositivo sem identificação no monitor '{ $monitor }'
+error-invalid-edid = EDID inválido no monitor '{ $monitor }': { $reason }
+error-physical-not-found = Monitor físico { $target } não encontrado em '{ $monitor }'
+error-ipc-server = Servidor JSON-RPC: { $reason }
+error-start-at-login = Inicialização automática: { $reason }
+error-tray = Bandeja: { $reason }
+capabilities-empty = String de capacidades vazia
+capabilities-unexpected-char = Caractere inesperado '{ $found }' na posição { $pos }
+capabilities-missing-value = Chave '{ $key }' sem valor na posição { $pos }
+edid-too-short = EDID com { $len } bytes, esperado pelo menos { $min }
+edid-invalid-header = Cabeçalho EDID inválido
+ddc-write-not-confirmed = Valor VCP { $code } não confirmado: escrito { $written }, lido { $read }
+ddc-code-disabled = Código VCP { $code } desativado para o modelo '{ $model }'
+monitor-not-found = Monitor '{ $monitor }' não encontrado
+brightness-out-of-range = O brilho deve estar entre 0 e 100
+invalid-vcp-code = Código VCP inválido '{ $code }'
+rpc-empty-batch = Lote vazio
+rpc-invalid-request = Requisição JSON-RPC 2.0 inválida
+rpc-method-not-found = Método '{ $method }' não existe
+rpc-missing-monitor = Parâmetro 'monitor' ausente
+rpc-invalid-param = Parâmetro '{ $key }' inválido
+
+## Linha de comando
+
+cli-physical-requires-number = --physical requer um número
+cli-invalid-physical = Monitor físico inválido '{ $value }'
+cli-unknown-option = Opção desconhecida '{ $option }'
+cli-invalid-mode = Modo inválido '{ $mode }', use LxA (ex.: 1920x1080)
+cli-invalid-number = Número inválido '{ $value }'
+cli-only-requires-list = --only requer uma lista de categorias (factory, luminance, geometry, color)
+cli-invalid-category = Categoria inválida '{ $value }'; use factory, luminance, geometry ou color
+cli-reset-needs-yes = A redefinição apaga os ajustes do monitor; repita com --yes para confirmar
+cli-primary-suffix = principal
+cli-manufacturer = Fabricante
+cli-model = Modelo
+cli-serial = Série
+cli-mode = Modo
+cli-position = Posição
+cli-primary = Principal
+cli-vcp-codes = Códigos VCP
+cli-usage =
+    Uso: DisplayManager <comando> [opções]
+
+    Comandos:
+      list                               Lista os monitores
+      info <monitor>                     Identificação, modo atual e suporte a DDC/CI
+      set-mode <monitor> <LxA>           Altera a resolução (ex.: 1920x1080)
+      move <monitor> <x> <y>             Move o monitor na área de trabalho
+      set-primary <monitor>              Define o monitor principal
+      brightness get <monitor>           Lê o brilho em %
+      brightness set <monitor> <0-100>   Altera o brilho em %
+      vcp get <monitor> <código>         Lê um código VCP (hexadecimal, ex.: 10 ou 0x10)
+      vcp set <monitor> <código> <valor> Escreve um código VCP
+      reset <monitor> --yes              Restaura os padrões de fábrica via VCP
+      inventory [arquivo.json|.csv]      Exporta o inventário de monitores
+
+    Opções:
+      --json            Saída em JSON
+      --physical <n>    Monitor físico (a partir de 0) para comandos VCP
+      --yes             Confirma o reset
+      --only <lista>    Categorias do reset: factory, luminance, geometry, color
+
+    <monitor> aceita o índice, o nome do dispositivo (DISPLAY2), o nome amigável ou o apelido.
+
+    Códigos de saída: 0 sucesso, 1 falha, 2 uso incorreto, 3 monitor não encontrado, 4 falha DDC/CI
+
+## Configurações
+
+settings = Configurações
+settings-font-size = Tamanho da fonte
+settings-window-size = Tamanho da janela
+settings-refresh = Atualização
+settings-refresh-manual = Só ao selecionar
+settings-refresh-after-changes = Após alterações
+settings-refresh-periodic = Periódica
+settings-refresh-interval = Intervalo
+settings-confirm-timeout = Confirmar resolução em
+settings-invalid-file = Configurações inválidas em { $path } ({ $reason }); usando os padrões
+settings-invalid-version = Campo 'version' inválido
+settings-newer-version = Configurações da versão { $version }, mais nova que a suportada ({ $supported }); alguns ajustes podem ser ignorados
+error-save-settings = Não foi possível salvar as configurações: { $reason }
+confirm-resolution = Manter resolução?
+confirm-resolution-countdown = Voltando à resolução anterior em { $seconds } s
+confirm-keep = Manter
+confirm-revert = Reverter
+
+## Abas
+
+tab-arrangement = Disposição
+tab-monitors = Monitores
+tab-vcp = VCP
+tab-profiles = Perfis
+tab-settings = Configurações
+position = Posição
+apply = Aplicar
+
+## Avisos
+
+log = Registro de erros
+log-toggle = ⚠ { $count ->
+        [one] 1 erro
+       *[other] { $count } erros
+    }
+log-copy = Copiar
+log-clear = Limpar
+error-brightness = Brilho não aplicado: { $reason }
+error-vcp-write = VCP { $code } não aplicado: { $reason }
+warning-monitor-disconnected = Monitor desconectado; a lista foi atualizada
+done-set-resolution = Resolução alterada
+done-set-primary = Monitor principal definido
+done-move = Monitor movido
+done-auto-adjust = Bordas ajustadas
+done-reset = Padrões de fábrica restaurados
+done-set-input = Entrada trocada
+done-set-power = Modo de energia alterado
+done-turn-off-others = Outros monitores desligados
+done-capture-profile = Perfil salvo
+done-apply-profile = Perfil aplicado
+done-capture-layout = Disposição salva
+done-apply-layout = Disposição aplicada
+
+## Redefinição de VCP
+
+cancel = Cancelar
+reset-intro = Escolha o que restaurar aos padrões de fábrica em { $monitor }. Ajustes feitos no menu do monitor serão perdidos.
+reset-factory = Padrões de fábrica
+reset-factory-description = Restaura todos os ajustes do monitor, incluindo os das demais categorias.
+reset-luminance-contrast = Brilho e contraste
+reset-luminance-contrast-description = Restaura apenas brilho e contraste.
+reset-geometry = Geometria
+reset-geometry-description = Restaura posição e tamanho da imagem (relevante em entradas analógicas).
+reset-color = Cor
+reset-color-description = Restaura predefinição de cor, ganhos, níveis de preto e gama.
+reset-backup-note = Os valores atuais são copiados antes; a redefinição pode ser desfeita enquanto o programa estiver aberto.
+reset-confirm = Redefinir
+reset-undo = Desfazer redefinição
+task-restore-vcp = Restaurando valores VCP
+done-restore-vcp = Valores anteriores restaurados
@@ -1 +1,19 @@
-pub mod functions;
\ No newline at end of file
+pub mod brightness;
+pub mod capabilities;
+pub mod cli;
+pub mod ddc;
+pub mod edid;
+pub mod functions;
+pub mod groups;
+pub mod hotkeys;
+pub mod i18n;
+pub mod inventory;
+pub mod ipc;
+pub mod layouts;
+pub mod notifications;
+pub mod profiles;
+pub mod schedule;
+pub mod settings;
+pub mod tray;
+pub mod vcp;
+pub mod worker;
\ No newline at end of file
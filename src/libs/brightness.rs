@@ -0,0 +1,380 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use windows::core::{Error, Result};
+
+use super::functions::{PhysicalTarget, brightness_maxima, set_brightness_scaled, set_vcp};
+use super::groups::{CurvePoint, map_level};
+use super::vcp::BRIGHTNESS;
+
+pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(100);
+// Intervalos sem escrita que encerram um arrasto.
+const SESSION_INTERVALS: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Level {
+    Percent(u32),
+    Raw(u32),
+}
+
+// Monitor e código VCP; Level::Percent só aparece com BRIGHTNESS.
+type Channel = (u32, PhysicalTarget, u8);
+
+enum Command {
+    Set { channel: Channel, level: Level },
+    Fade { channel: Channel, from: u32, to: u32, duration: Duration, curve: Vec<CurvePoint> },
+}
+
+struct Fade {
+    from: u32,
+    to: u32,
+    start: Instant,
+    duration: Duration,
+    curve: Vec<CurvePoint>,
+}
+
+impl Fade {
+    fn value_at(&self, now: Instant) -> u32 {
+        let elapsed = now.saturating_duration_since(self.start);
+        if self.duration.is_zero() || elapsed >= self.duration {
+            return self.to;
+        }
+        let t = elapsed.as_secs_f64() / self.duration.as_secs_f64();
+        let value = self.from as f64 + (self.to as f64 - self.from as f64) * t;
+        value.round() as u32
+    }
+
+    fn finished(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.start) >= self.duration
+    }
+
+    // Sem curva escreve o percentual; com curva, o valor calibrado do nível.
+    fn level_at(&self, now: Instant) -> Level {
+        let value = self.value_at(now);
+        map_level(&self.curve, value).map_or(Level::Percent(value), Level::Raw)
+    }
+}
+
+// Escritas feitas pela thread do controlador; os testes trocam o DDC por um registro.
+trait Writer: Send + 'static {
+    fn maxima(&mut self, index: u32, target: PhysicalTarget) -> Result<Vec<u32>>;
+    fn brightness(&mut self, index: u32, target: PhysicalTarget, percent: u32, maxima: &[u32]) -> Result<()>;
+    fn vcp(&mut self, index: u32, target: PhysicalTarget, code: u8, value: u32) -> Result<()>;
+}
+
+struct Ddc;
+
+impl Writer for Ddc {
+    fn maxima(&mut self, index: u32, target: PhysicalTarget) -> Result<Vec<u32>> {
+        brightness_maxima(index, target)
+    }
+
+    fn brightness(&mut self, index: u32, target: PhysicalTarget, percent: u32, maxima: &[u32]) -> Result<()> {
+        set_brightness_scaled(index, target, percent, maxima)
+    }
+
+    fn vcp(&mut self, index: u32, target: PhysicalTarget, code: u8, value: u32) -> Result<()> {
+        set_vcp(index, target, code, value)
+    }
+}
+
+// Além do brilho, serve a qualquer controle contínuo que precise de escritas espaçadas.
+pub struct BrightnessController {
+    sender: Sender<Command>,
+    failures: Receiver<(u32, u8, Error)>,
+}
+
+impl BrightnessController {
+    pub fn new(interval: Duration) -> Self {
+        Self::with_writer(interval, Ddc)
+    }
+
+    fn with_writer(interval: Duration, writer: impl Writer) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let (failures_sender, failures) = mpsc::channel();
+        thread::spawn(move || run(receiver, failures_sender, interval, writer));
+        Self { sender, failures }
+    }
+
+    // Escritas que falharam desde a última chamada, com o índice do monitor e o código.
+    pub fn failures(&self) -> Vec<(u32, u8, Error)> {
+        self.failures.try_iter().collect()
+    }
+
+    pub fn set(&self, index: u32, target: PhysicalTarget, percent: u32) {
+        let _ = self.sender.send(Command::Set {
+            channel: (index, target, BRIGHTNESS),
+            level: Level::Percent(percent.min(100)),
+        });
+    }
+
+    pub fn set_raw(&self, index: u32, target: PhysicalTarget, value: u32) {
+        self.set_vcp(index, target, BRIGHTNESS, value);
+    }
+
+    pub fn set_vcp(&self, index: u32, target: PhysicalTarget, code: u8, value: u32) {
+        let _ = self.sender.send(Command::Set { channel: (index, target, code), level: Level::Raw(value) });
+    }
+
+    pub fn fade(&self, index: u32, target: PhysicalTarget, from: u32, to: u32, duration: Duration) {
+        self.fade_curve(index, target, from, to, duration, &[]);
+    }
+
+    // Transição em níveis de 0 a 100, cada passo convertido pela curva de calibração do grupo.
+    pub fn fade_curve(&self, index: u32, target: PhysicalTarget, from: u32, to: u32, duration: Duration, curve: &[CurvePoint]) {
+        let _ = self.sender.send(Command::Fade {
+            channel: (index, target, BRIGHTNESS),
+            from: from.min(100),
+            to: to.min(100),
+            duration,
+            curve: curve.to_vec(),
+        });
+    }
+}
+
+impl Default for BrightnessController {
+    fn default() -> Self {
+        Self::new(DEFAULT_INTERVAL)
+    }
+}
+
+fn run(receiver: Receiver<Command>, failures: Sender<(u32, u8, Error)>, interval: Duration, mut writer: impl Writer) {
+    let mut targets: HashMap<Channel, Level> = HashMap::new();
+    let mut fades: HashMap<Channel, Fade> = HashMap::new();
+    let mut written: HashMap<Channel, Level> = HashMap::new();
+    let mut last_write: HashMap<Channel, Instant> = HashMap::new();
+    // Máximos lidos na primeira escrita de um arrasto e reaproveitados até ele parar.
+    let mut maxima: HashMap<Channel, Vec<u32>> = HashMap::new();
+    let session = interval * SESSION_INTERVALS;
+    // Avisa só na primeira falha seguida de cada controle, não a cada passo do slider.
+    let mut failing: HashSet<Channel> = HashSet::new();
+    let mut connected = true;
+
+    while connected || !targets.is_empty() || !fades.is_empty() {
+        let timeout = if targets.is_empty() && fades.is_empty() {
+            Duration::from_secs(3600)
+        } else {
+            interval
+        };
+
+        // Só o último valor pedido para cada controle interessa.
+        let mut next = if connected {
+            receiver.recv_timeout(timeout)
+        } else {
+            thread::sleep(interval);
+            Err(RecvTimeoutError::Timeout)
+        };
+        loop {
+            match next {
+                Ok(Command::Set { channel, level }) => {
+                    fades.remove(&channel);
+                    written.remove(&channel);
+                    targets.insert(channel, level);
+                }
+                Ok(Command::Fade { channel, from, to, duration, curve }) => {
+                    targets.remove(&channel);
+                    written.remove(&channel);
+                    fades.insert(channel, Fade { from, to, start: Instant::now(), duration, curve });
+                }
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    connected = false;
+                    break;
+                }
+            }
+            next = receiver.try_recv().map_err(|e| match e {
+                mpsc::TryRecvError::Empty => RecvTimeoutError::Timeout,
+                mpsc::TryRecvError::Disconnected => RecvTimeoutError::Disconnected,
+            });
+        }
+
+        let now = Instant::now();
+        for (channel, fade) in &fades {
+            targets.insert(*channel, fade.level_at(now));
+        }
+        fades.retain(|_, fade| !fade.finished(now));
+
+        maxima.retain(|channel, _| last_write.get(channel).is_some_and(|t| now.duration_since(*t) < session));
+
+        let due: Vec<(Channel, Level)> = targets
+            .iter()
+            .filter(|(channel, _)| last_write.get(channel).is_none_or(|t| now.duration_since(*t) >= interval))
+            .map(|(channel, level)| (*channel, *level))
+            .collect();
+
+        for (channel, level) in due {
+            targets.remove(&channel);
+            if written.get(&channel) == Some(&level) {
+                continue;
+            }
+
+            let (index, target, code) = channel;
+            last_write.insert(channel, Instant::now());
+            let result = match level {
+                Level::Percent(percent) => match maxima.get(&channel) {
+                    Some(max) => writer.brightness(index, target, percent, max),
+                    None => writer.maxima(index, target).and_then(|max| {
+                        writer.brightness(index, target, percent, maxima.entry(channel).or_insert(max))
+                    }),
+                },
+                Level::Raw(value) => writer.vcp(index, target, code, value),
+            };
+            match result {
+                Ok(()) => {
+                    failing.remove(&channel);
+                    written.insert(channel, level);
+                }
+                Err(e) => {
+                    // O monitor pode ter sido trocado; o próximo arrasto relê os máximos.
+                    maxima.remove(&channel);
+                    if failing.insert(channel) {
+                        let _ = failures.send((index, code, e));
+                    }
+                    written.remove(&channel);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use windows::core::HRESULT;
+
+    use super::*;
+    use crate::libs::vcp::CONTRAST;
+
+    const INTERVAL: Duration = Duration::from_millis(20);
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    enum Write {
+        Maxima,
+        Brightness(u32, Vec<u32>),
+        Vcp(u8, u32),
+    }
+
+    #[derive(Default)]
+    struct Log {
+        writes: Vec<Write>,
+        fail_next: bool,
+    }
+
+    // Registra as escritas em vez de falar com o monitor; dois físicos com máximos diferentes.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Log>>);
+
+    impl Recorder {
+        fn writes(&self) -> Vec<Write> {
+            self.0.lock().unwrap().writes.clone()
+        }
+
+        fn brightness(&self) -> Vec<u32> {
+            self.writes().into_iter().filter_map(|w| if let Write::Brightness(p, _) = w { Some(p) } else { None }).collect()
+        }
+
+        fn maxima_reads(&self) -> usize {
+            self.writes().iter().filter(|w| **w == Write::Maxima).count()
+        }
+
+        // Espera a thread escrever `last` e mais alguns intervalos, para pegar escritas atrasadas.
+        fn wait_for(&self, last: &Write) {
+            let start = Instant::now();
+            while self.writes().last() != Some(last) {
+                assert!(start.elapsed() < Duration::from_secs(5), "{last:?} não foi escrito: {:?}", self.writes());
+                thread::sleep(Duration::from_millis(5));
+            }
+            thread::sleep(INTERVAL * 3);
+        }
+    }
+
+    impl Writer for Recorder {
+        fn maxima(&mut self, _: u32, _: PhysicalTarget) -> Result<Vec<u32>> {
+            self.0.lock().unwrap().writes.push(Write::Maxima);
+            Ok(vec![100, 255])
+        }
+
+        fn brightness(&mut self, _: u32, _: PhysicalTarget, percent: u32, maxima: &[u32]) -> Result<()> {
+            let mut log = self.0.lock().unwrap();
+            log.writes.push(Write::Brightness(percent, maxima.to_vec()));
+            if std::mem::take(&mut log.fail_next) {
+                return Err(Error::new(HRESULT::from_win32(0x1F), "sem resposta"));
+            }
+            Ok(())
+        }
+
+        fn vcp(&mut self, _: u32, _: PhysicalTarget, code: u8, value: u32) -> Result<()> {
+            self.0.lock().unwrap().writes.push(Write::Vcp(code, value));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn burst_collapses_to_last_value() {
+        let recorder = Recorder::default();
+        let controller = BrightnessController::with_writer(INTERVAL, recorder.clone());
+        for percent in 0..=100 {
+            controller.set(0, PhysicalTarget::All, percent);
+        }
+        recorder.wait_for(&Write::Brightness(100, vec![100, 255]));
+
+        // A thread pode acordar com o primeiro valor antes do resto chegar; os do meio nunca são escritos.
+        let written = recorder.brightness();
+        assert!(written == [100] || written == [0, 100], "{written:?}");
+        assert_eq!(recorder.maxima_reads(), 1);
+    }
+
+    #[test]
+    fn raw_burst_collapses_without_reading_maxima() {
+        let recorder = Recorder::default();
+        let controller = BrightnessController::with_writer(INTERVAL, recorder.clone());
+        for value in 0..50 {
+            controller.set_vcp(0, PhysicalTarget::All, CONTRAST, value);
+        }
+        recorder.wait_for(&Write::Vcp(CONTRAST, 49));
+
+        let writes = recorder.writes();
+        assert!(writes == [Write::Vcp(CONTRAST, 49)] || writes == [Write::Vcp(CONTRAST, 0), Write::Vcp(CONTRAST, 49)], "{writes:?}");
+    }
+
+    #[test]
+    fn reads_maxima_once_per_drag() {
+        let recorder = Recorder::default();
+        let controller = BrightnessController::with_writer(INTERVAL, recorder.clone());
+        // Valores espaçados mais que o intervalo: todos são escritos, com os máximos da primeira leitura.
+        for percent in [10, 20, 30, 40] {
+            controller.set(0, PhysicalTarget::All, percent);
+            thread::sleep(INTERVAL * 2);
+        }
+        recorder.wait_for(&Write::Brightness(40, vec![100, 255]));
+        assert_eq!(recorder.brightness(), [10, 20, 30, 40]);
+        assert_eq!(recorder.maxima_reads(), 1);
+
+        // Outro arrasto, depois de uma pausa, relê.
+        thread::sleep(INTERVAL * (SESSION_INTERVALS + 2));
+        controller.set(0, PhysicalTarget::All, 50);
+        recorder.wait_for(&Write::Brightness(50, vec![100, 255]));
+        assert_eq!(recorder.maxima_reads(), 2);
+        assert!(controller.failures().is_empty());
+    }
+
+    #[test]
+    fn failed_write_rereads_maxima() {
+        let recorder = Recorder::default();
+        let controller = BrightnessController::with_writer(INTERVAL, recorder.clone());
+        controller.set(0, PhysicalTarget::All, 10);
+        recorder.wait_for(&Write::Brightness(10, vec![100, 255]));
+
+        recorder.0.lock().unwrap().fail_next = true;
+        controller.set(0, PhysicalTarget::All, 20);
+        recorder.wait_for(&Write::Brightness(20, vec![100, 255]));
+        controller.set(0, PhysicalTarget::All, 30);
+        recorder.wait_for(&Write::Brightness(30, vec![100, 255]));
+
+        assert_eq!(recorder.maxima_reads(), 2);
+        assert_eq!(controller.failures().len(), 1);
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::i18n::t;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    pub prot: Option<String>,
+    pub kind: Option<String>,
+    pub model: Option<String>,
+    pub mccs_ver: Option<String>,
+    pub cmds: Vec<u8>,
+    pub vcp: BTreeMap<u8, Vec<u8>>,
+    pub other: Vec<(String, String)>,
+}
+
+impl Capabilities {
+    pub fn supports(&self, code: u8) -> bool {
+        self.vcp.contains_key(&code)
+    }
+
+    pub fn values(&self, code: u8) -> Option<&[u8]> {
+        self.vcp.get(&code).map(|v| v.as_slice()).filter(|v| !v.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CapabilitiesError {
+    Empty,
+    UnexpectedChar { pos: usize, found: char },
+    MissingValue { pos: usize, key: String },
+}
+
+impl fmt::Display for CapabilitiesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CapabilitiesError::Empty => write!(f, "{}", t!("capabilities-empty")),
+            CapabilitiesError::UnexpectedChar { pos, found } => {
+                write!(f, "{}", t!("capabilities-unexpected-char", found = found.escape_default().to_string(), pos = *pos))
+            }
+            CapabilitiesError::MissingValue { pos, key } => {
+                write!(f, "{}", t!("capabilities-missing-value", key = key.as_str(), pos = *pos))
+            }
+        }
+    }
+}
+
+impl std::error::Error for CapabilitiesError {}
+
+pub fn parse_capabilities(input: &str) -> Result<Capabilities, CapabilitiesError> {
+    let bytes = input.trim_matches(|c: char| c.is_whitespace() || c == '\0').as_bytes();
+    if bytes.is_empty() {
+        return Err(CapabilitiesError::Empty);
+    }
+
+    // Alguns monitores omitem os parênteses externos ou não os fecham.
+    let body = if bytes[0] == b'(' {
+        let end = matching_paren(bytes, 0).unwrap_or(bytes.len());
+        &bytes[1..end]
+    } else {
+        bytes
+    };
+
+    let mut caps = Capabilities::default();
+    let mut pos = 0;
+
+    while pos < body.len() {
+        let c = body[pos];
+        // Um ')' sobrando não muda o significado do resto.
+        if c.is_ascii_whitespace() || c == b'\0' || c == b')' {
+            pos += 1;
+            continue;
+        }
+        if !is_key_byte(c) {
+            return Err(unexpected(body, pos));
+        }
+
+        let key_start = pos;
+        while pos < body.len() && is_key_byte(body[pos]) {
+            pos += 1;
+        }
+        let key = String::from_utf8_lossy(&body[key_start..pos]).to_ascii_lowercase();
+
+        while pos < body.len() && body[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        if pos >= body.len() || body[pos] != b'(' {
+            return Err(CapabilitiesError::MissingValue { pos: key_start, key });
+        }
+
+        // Strings truncadas terminam sem o ')' final; o valor vai até o fim.
+        let close = matching_paren(body, pos);
+        let value = &body[pos + 1..close.unwrap_or(body.len())];
+        pos = close.map_or(body.len(), |c| c + 1);
+
+        match key.as_str() {
+            "prot" => caps.prot = Some(text(value)),
+            "type" => caps.kind = Some(text(value)),
+            "model" => caps.model = Some(text(value)),
+            "mccs_ver" => caps.mccs_ver = Some(text(value)),
+            "cmds" => caps.cmds = parse_hex_list(value),
+            "vcp" => {
+                for (code, values) in parse_vcp(value) {
+                    caps.vcp.entry(code).or_default().extend(values);
+                }
+            }
+            _ => caps.other.push((key, text(value))),
+        }
+    }
+
+    Ok(caps)
+}
+
+fn parse_vcp(value: &[u8]) -> Vec<(u8, Vec<u8>)> {
+    let mut codes: Vec<(u8, Vec<u8>)> = Vec::new();
+    // Índice do último código válido; None depois de um token inválido,
+    // para descartar também a lista de valores que o acompanha.
+    let mut current: Option<usize> = None;
+    let mut pos = 0;
+
+    while pos < value.len() {
+        let c = value[pos];
+        if c.is_ascii_whitespace() || c == b'\0' || c == b')' {
+            pos += 1;
+        } else if c == b'(' {
+            let close = matching_paren(value, pos);
+            let inner = &value[pos + 1..close.unwrap_or(value.len())];
+            pos = close.map_or(value.len(), |c| c + 1);
+
+            if let Some(index) = current {
+                codes[index].1.extend(parse_hex_list(inner));
+            }
+        } else {
+            let start = pos;
+            while pos < value.len() && !is_separator(value[pos]) {
+                pos += 1;
+            }
+            current = hex_pairs(&value[start..pos]).map(|list| {
+                codes.extend(list.into_iter().map(|code| (code, Vec::new())));
+                codes.len() - 1
+            });
+        }
+    }
+
+    codes
+}
+
+// Um token inválido descarta só a si mesmo, não a string inteira.
+fn parse_hex_list(value: &[u8]) -> Vec<u8> {
+    // Listas aninhadas (ex.: sub-valores de 0x14 em alguns modelos) são achatadas.
+    value
+        .split(|b| is_separator(*b))
+        .filter(|t| !t.is_empty())
+        .filter_map(hex_pairs)
+        .flatten()
+        .collect()
+}
+
+fn hex_pairs(token: &[u8]) -> Option<Vec<u8>> {
+    // Alguns firmwares concatenam os códigos sem espaço ("021012").
+    if !token.len().is_multiple_of(2) || !token.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+    }
+
+    token
+        .chunks(2)
+        .map(|pair| std::str::from_utf8(pair).ok().and_then(|s| u8::from_str_radix(s, 16).ok()))
+        .collect()
+}
+
+fn is_separator(b: u8) -> bool {
+    b.is_ascii_whitespace() || b == b'(' || b == b')' || b == b'\0'
+}
+
+fn matching_paren(bytes: &[u8], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, b) in bytes.iter().enumerate().skip(open) {
+        match b {
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn is_key_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+fn text(value: &[u8]) -> String {
+    String::from_utf8_lossy(value).trim().to_string()
+}
+
+fn unexpected(bytes: &[u8], pos: usize) -> CapabilitiesError {
+    let found = String::from_utf8_lossy(&bytes[pos.min(bytes.len())..])
+        .chars()
+        .next()
+        .unwrap_or('\0');
+    CapabilitiesError::UnexpectedChar { pos, found }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DELL: &str = "(prot(monitor)type(LCD)model(U2415)cmds(01 02 03 07 0C E3 F3)vcp(02 04 05 08 10 12 14(05 08 0B 0C) \
+        16 18 1A 52 60(01 0F 11) AA(01 02) AC AE B2 B6 C6 C8 C9 D6(01 04 05) DC(00 02 03 05) DF E0 E1 E2(00 01 02 04 0E 12 14) \
+        F0(0C) F1 F2 FD)mswhql(1)asset_eep(40)mccs_ver(2.1))";
+    const LG: &str = "(prot(monitor)type(LCD)model(LG FULL HD)cmds(01 02 03 0C E3 F3)vcp(02 04 05 08 10 12 14(05 08 0B) 16 18 1A \
+        52 60(01 03 04 0F 10 11 12) 6C 6E 70 8D(01 02) B6 C0 C6 C8 C9 D6(01 04) DF FE)mccs_ver(2.1)mswhql(1))";
+    const SAMSUNG: &str = "(prot(monitor)type(LCD)model(S24D300)cmds(01 02 03 07 0C E3 F3)vcp(02 04 05 08 10 12 14(05 08 0B 0C) \
+        16 18 1A 52 60(01 03 04)62 AC AE B2 B6 C6 C8 C9 CC(01 02 03 04 05 06 07 08 09 0A 0C 0D 11 12 14 1A 1E 1F 23 72 73) \
+        D6(01 04 05) DC(00 01 02 03 04 05) DF FD)mccs_ver(2.0)mswhql(1))\0";
+
+    #[test]
+    fn parses_dell() {
+        let caps = parse_capabilities(DELL).unwrap();
+        assert_eq!(caps.prot.as_deref(), Some("monitor"));
+        assert_eq!(caps.kind.as_deref(), Some("LCD"));
+        assert_eq!(caps.model.as_deref(), Some("U2415"));
+        assert_eq!(caps.mccs_ver.as_deref(), Some("2.1"));
+        assert_eq!(caps.cmds, [0x01, 0x02, 0x03, 0x07, 0x0C, 0xE3, 0xF3]);
+        assert!(caps.supports(0x10));
+        assert_eq!(caps.values(0x60), Some(&[0x01, 0x0F, 0x11][..]));
+        assert_eq!(caps.values(0xF0), Some(&[0x0C][..]));
+        assert_eq!(caps.values(0x10), None);
+        assert_eq!(caps.vcp.len(), 30);
+        assert_eq!(
+            caps.other,
+            [("mswhql".to_string(), "1".to_string()), ("asset_eep".to_string(), "40".to_string())]
+        );
+    }
+
+    #[test]
+    fn parses_lg() {
+        let caps = parse_capabilities(LG).unwrap();
+        assert_eq!(caps.model.as_deref(), Some("LG FULL HD"));
+        assert_eq!(caps.values(0x14), Some(&[0x05, 0x08, 0x0B][..]));
+        assert_eq!(caps.values(0x60), Some(&[0x01, 0x03, 0x04, 0x0F, 0x10, 0x11, 0x12][..]));
+        assert!(caps.supports(0xFE));
+    }
+
+    #[test]
+    fn parses_samsung() {
+        // Código colado ao parêntese anterior ("60(01 03 04)62") e NUL no final.
+        let caps = parse_capabilities(SAMSUNG).unwrap();
+        assert_eq!(caps.mccs_ver.as_deref(), Some("2.0"));
+        assert_eq!(caps.values(0x60), Some(&[0x01, 0x03, 0x04][..]));
+        assert!(caps.supports(0x62));
+        assert_eq!(caps.values(0x62), None);
+        assert_eq!(caps.values(0xCC).map(<[u8]>::len), Some(21));
+    }
+
+    #[test]
+    fn parses_concatenated_codes() {
+        let caps = parse_capabilities("vcp(021012(00 01))").unwrap();
+        assert_eq!(caps.vcp.keys().copied().collect::<Vec<_>>(), [0x02, 0x10, 0x12]);
+        assert_eq!(caps.values(0x12), Some(&[0x00, 0x01][..]));
+        assert_eq!(caps.values(0x10), None);
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert_eq!(parse_capabilities(""), Err(CapabilitiesError::Empty));
+        assert_eq!(parse_capabilities(" \0\0 "), Err(CapabilitiesError::Empty));
+    }
+
+    #[test]
+    fn accepts_truncated_input() {
+        let caps = parse_capabilities("(prot(monitor)type(LCD)vcp(10 12 14(05 08").unwrap();
+        assert_eq!(caps.kind.as_deref(), Some("LCD"));
+        assert!(caps.supports(0x10));
+        assert_eq!(caps.values(0x14), Some(&[0x05, 0x08][..]));
+    }
+
+    #[test]
+    fn accepts_unbalanced_parentheses() {
+        // Parêntese externo ausente e um ')' sobrando depois da lista de VCP.
+        let caps = parse_capabilities("prot(monitor)vcp(10 12 14(01)))mccs_ver(2.2)").unwrap();
+        assert_eq!(caps.prot.as_deref(), Some("monitor"));
+        assert_eq!(caps.values(0x14), Some(&[0x01][..]));
+        assert_eq!(caps.mccs_ver.as_deref(), Some("2.2"));
+    }
+
+    #[test]
+    fn skips_invalid_hex_tokens() {
+        let caps = parse_capabilities("(cmds(01 ZZ 03 0)vcp(10 XY(01 02) 12 G0 14(05 QQ 08) 1 16))").unwrap();
+        assert_eq!(caps.cmds, [0x01, 0x03]);
+        assert_eq!(caps.vcp.keys().copied().collect::<Vec<_>>(), [0x10, 0x12, 0x14, 0x16]);
+        // A lista de valores de um código inválido não vai para o código anterior.
+        assert_eq!(caps.values(0x10), None);
+        assert_eq!(caps.values(0x14), Some(&[0x05, 0x08][..]));
+    }
+
+    #[test]
+    fn rejects_key_without_value() {
+        assert_eq!(
+            parse_capabilities("(prot(monitor)model)"),
+            Err(CapabilitiesError::MissingValue { pos: 13, key: "model".to_string() })
+        );
+    }
+
+    #[test]
+    fn rejects_unexpected_character() {
+        assert_eq!(
+            parse_capabilities("(prot(monitor)#type(LCD))"),
+            Err(CapabilitiesError::UnexpectedChar { pos: 13, found: '#' })
+        );
+    }
+}
@@ -0,0 +1,364 @@
+use std::path::PathBuf;
+
+use serde_json::{Value, json};
+use windows::Win32::System::Console::{ATTACH_PARENT_PROCESS, AttachConsole};
+
+use super::functions::{
+    DisplaySummary,
+    PhysicalTarget,
+    configure_ddc,
+    display_brightness,
+    get_vcp,
+    list_displays,
+    move_display,
+    reset_monitor,
+    set_primary_display,
+    set_resolution,
+    set_vcp,
+};
+use super::i18n::{set_language, t};
+use super::inventory::{
+    InventoryFormat,
+    default_inventory_path,
+    display_record,
+    export_inventory,
+    find_display,
+    list_inventory,
+};
+use super::settings::{Settings, load_settings};
+use super::vcp::{BRIGHTNESS, ResetCategory, VcpKind};
+
+// Códigos de saída estáveis: scripts dependem deles, não reordenar.
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_FAILED: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+pub const EXIT_NOT_FOUND: i32 = 3;
+pub const EXIT_DDC: i32 = 4;
+
+const COMMANDS: [&str; 11] = [
+    "list",
+    "info",
+    "set-mode",
+    "move",
+    "set-primary",
+    "brightness",
+    "vcp",
+    "reset",
+    "inventory",
+    "help",
+    "--export-inventory",
+];
+
+struct CliError {
+    code: i32,
+    message: String,
+}
+
+impl CliError {
+    fn usage(message: impl Into<String>) -> Self {
+        Self { code: EXIT_USAGE, message: message.into() }
+    }
+
+    fn failed(error: windows::core::Error) -> Self {
+        Self { code: EXIT_FAILED, message: error.message() }
+    }
+
+    fn ddc(error: windows::core::Error) -> Self {
+        Self { code: EXIT_DDC, message: error.message() }
+    }
+}
+
+// Opções seguidas de um valor.
+const VALUE_OPTIONS: [&str; 2] = ["--physical", "--only"];
+
+struct Options {
+    json: bool,
+    physical: PhysicalTarget,
+    yes: bool,
+    only: Option<Vec<ResetCategory>>,
+}
+
+// Opções podem vir antes do comando ("--json list"); decide a primeira palavra que não é opção.
+pub fn is_cli(args: &[String]) -> bool {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--export-inventory" => return true,
+            option if VALUE_OPTIONS.contains(&option) => {
+                iter.next();
+            }
+            option if option.starts_with("--") => {}
+            command => return COMMANDS.contains(&command),
+        }
+    }
+    false
+}
+
+pub fn run(args: &[String]) -> i32 {
+    // Em release o executável é do subsistema Windows e nasce sem console.
+    unsafe {
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+
+    let mut settings = load_settings();
+    set_language(settings.language);
+    configure_ddc(settings.ddc.clone());
+    // Só em memória: quem grava a conversão é a janela ou o processo em segundo plano.
+    settings.migrate_monitor_keys(&list_displays().unwrap_or_default());
+
+    let result = parse_args(args).and_then(|(options, positional)| execute(&positional, &options, &settings));
+    match result {
+        Ok(()) => EXIT_OK,
+        // Com --json até os erros de uso saem em JSON, para o script não ter de tratar texto.
+        Err(e) if args.iter().any(|arg| arg == "--json") => {
+            print_json(&json!({ "error": e.message, "code": e.code }));
+            e.code
+        }
+        Err(e) => {
+            eprintln!("{}", e.message);
+            e.code
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<(Options, Vec<String>), CliError> {
+    let mut options = Options { json: false, physical: PhysicalTarget::All, yes: false, only: None };
+    let mut positional = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--json" => options.json = true,
+            "--physical" => {
+                let value = iter.next().ok_or_else(|| CliError::usage(t!("cli-physical-requires-number")))?;
+                let index = value.parse().map_err(|_| CliError::usage(t!("cli-invalid-physical", value = value.as_str())))?;
+                options.physical = PhysicalTarget::One(index);
+            }
+            "--yes" => options.yes = true,
+            "--only" => {
+                let value = iter.next().ok_or_else(|| CliError::usage(t!("cli-only-requires-list")))?;
+                let categories = value
+                    .split(',')
+                    .filter(|name| !name.trim().is_empty())
+                    .map(|name| ResetCategory::from_name(name).ok_or_else(|| CliError::usage(t!("cli-invalid-category", value = name.trim()))))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if categories.is_empty() {
+                    return Err(CliError::usage(t!("cli-only-requires-list")));
+                }
+                options.only = Some(categories);
+            }
+            "--export-inventory" => positional.push("inventory".to_string()),
+            flag if flag.starts_with("--") => {
+                return Err(CliError::usage(format!("{}\n\n{}", t!("cli-unknown-option", option = flag), t!("cli-usage"))));
+            }
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    Ok((options, positional))
+}
+
+fn execute(positional: &[String], options: &Options, settings: &Settings) -> Result<(), CliError> {
+    let args: Vec<&str> = positional.iter().map(String::as_str).collect();
+    let physical = options.physical;
+
+    match args.as_slice() {
+        ["list"] => list(options, settings),
+        ["info", monitor] => {
+            let display = resolve(monitor, settings)?;
+            let record = display_record(display, &settings.nicknames).map_err(CliError::failed)?;
+            if options.json {
+                print_json(&json!(record));
+            } else {
+                let field = |id: &str, value: &str| println!("  {:<14}{}", format!("{}:", t!(id)), value);
+                let yes_no = |value: bool| if value { t!("yes") } else { t!("no") };
+                println!("{} {}", record.device, record.name);
+                field("cli-manufacturer", record.manufacturer.as_deref().unwrap_or("—"));
+                field("cli-model", record.model.as_deref().unwrap_or("—"));
+                field("cli-serial", record.serial.as_deref().unwrap_or("—"));
+                field("cli-mode", &format!("{}x{}", record.resolution.w, record.resolution.h));
+                field("cli-position", &format!("{},{}", record.position.left, record.position.top));
+                field("cli-primary", &yes_no(record.primary));
+                field("DDC/CI", &yes_no(record.ddc));
+                if !record.vcp_codes.is_empty() {
+                    field("cli-vcp-codes", &record.vcp_codes.join(" "));
+                }
+            }
+            Ok(())
+        }
+        ["set-mode", monitor, mode] => {
+            let display = resolve(monitor, settings)?;
+            let (w, h) = mode
+                .split_once(['x', 'X'])
+                .and_then(|(w, h)| Some((w.trim().parse().ok()?, h.trim().parse().ok()?)))
+                .ok_or_else(|| CliError::usage(t!("cli-invalid-mode", mode = *mode)))?;
+            set_resolution(display.index, w, h).map_err(CliError::failed)?;
+            done(options)
+        }
+        ["move", monitor, x, y] => {
+            let display = resolve(monitor, settings)?;
+            let x = parse_number(x)?;
+            let y = parse_number(y)?;
+            move_display(display.index, x, y).map_err(CliError::failed)?;
+            done(options)
+        }
+        ["set-primary", monitor] => {
+            let display = resolve(monitor, settings)?;
+            set_primary_display(display.index).map_err(CliError::failed)?;
+            done(options)
+        }
+        ["brightness", "get", monitor] => {
+            let display = resolve(monitor, settings)?;
+            let value = get_vcp(display.index, physical, BRIGHTNESS).map_err(CliError::ddc)?;
+            if options.json {
+                print_json(&json!({ "brightness": value.percent(), "current": value.current, "max": value.max }));
+            } else {
+                println!("{}", value.percent());
+            }
+            Ok(())
+        }
+        ["brightness", "set", monitor, percent] => {
+            let display = resolve(monitor, settings)?;
+            let percent: u32 = parse_number(percent)?;
+            if percent > 100 {
+                return Err(CliError::usage(t!("brightness-out-of-range")));
+            }
+            display_brightness(display.index, physical, percent).map_err(CliError::ddc)?;
+            done(options)
+        }
+        ["vcp", "get", monitor, code] => {
+            let display = resolve(monitor, settings)?;
+            let code = parse_code(code)?;
+            let value = get_vcp(display.index, physical, code).map_err(CliError::ddc)?;
+            if options.json {
+                let kind = match value.kind {
+                    VcpKind::SetParameter => "set_parameter",
+                    VcpKind::Momentary => "momentary",
+                };
+                print_json(&json!({ "code": format!("0x{:02X}", code), "current": value.current, "max": value.max, "kind": kind }));
+            } else {
+                println!("{} {}", value.current, value.max);
+            }
+            Ok(())
+        }
+        ["vcp", "set", monitor, code, value] => {
+            let display = resolve(monitor, settings)?;
+            let code = parse_code(code)?;
+            let value = parse_number(value)?;
+            set_vcp(display.index, physical, code, value).map_err(CliError::ddc)?;
+            done(options)
+        }
+        ["reset", monitor] => {
+            let display = resolve(monitor, settings)?;
+            if !options.yes {
+                return Err(CliError::usage(t!("cli-reset-needs-yes")));
+            }
+            let categories = options.only.as_deref().unwrap_or(&ResetCategory::ALL);
+            reset_monitor(display.index, physical, categories).map_err(CliError::ddc)?;
+            done(options)
+        }
+        ["inventory"] => export(default_inventory_path(InventoryFormat::Json), options, settings),
+        ["inventory", path] => export(PathBuf::from(path), options, settings),
+        [] | ["help"] => {
+            println!("{}", t!("cli-usage"));
+            Ok(())
+        }
+        _ => Err(CliError::usage(t!("cli-usage"))),
+    }
+}
+
+fn list(options: &Options, settings: &Settings) -> Result<(), CliError> {
+    let listings = list_inventory(&settings.nicknames).map_err(CliError::failed)?;
+
+    if options.json {
+        print_json(&json!(listings));
+    } else {
+        for d in listings {
+            println!(
+                "{}  {}  {}  {}x{} @ {},{}{}",
+                d.index,
+                d.device,
+                d.label,
+                d.width,
+                d.height,
+                d.left,
+                d.top,
+                if d.primary { format!("  ({})", t!("cli-primary-suffix")) } else { String::new() },
+            );
+        }
+    }
+    Ok(())
+}
+
+fn export(path: PathBuf, options: &Options, settings: &Settings) -> Result<(), CliError> {
+    export_inventory(&path, &settings.nicknames).map_err(CliError::failed)?;
+    if options.json {
+        print_json(&json!({ "ok": true, "path": path }));
+    } else {
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+fn resolve(selector: &str, settings: &Settings) -> Result<DisplaySummary, CliError> {
+    find_display(selector, &settings.nicknames)
+        .map_err(CliError::failed)?
+        .ok_or_else(|| CliError {
+            code: EXIT_NOT_FOUND,
+            message: t!("monitor-not-found", monitor = selector),
+        })
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, CliError> {
+    value.trim().parse().map_err(|_| CliError::usage(t!("cli-invalid-number", value = value)))
+}
+
+fn parse_code(value: &str) -> Result<u8, CliError> {
+    let hex = value.trim().trim_start_matches("0x").trim_start_matches("0X");
+    u8::from_str_radix(hex, 16).map_err(|_| CliError::usage(t!("invalid-vcp-code", code = value)))
+}
+
+fn done(options: &Options) -> Result<(), CliError> {
+    if options.json {
+        print_json(&json!({ "ok": true }));
+    }
+    Ok(())
+}
+
+fn print_json(value: &Value) {
+    println!("{}", serde_json::to_string_pretty(value).unwrap_or_default());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn detects_command_after_options() {
+        assert!(is_cli(&args("list")));
+        assert!(is_cli(&args("--json list")));
+        assert!(is_cli(&args("--physical 1 --json vcp get 0 10")));
+        assert!(is_cli(&args("--only color --yes reset 1")));
+        assert!(is_cli(&args("--export-inventory")));
+        assert!(!is_cli(&args("")));
+        assert!(!is_cli(&args("--background")));
+        assert!(!is_cli(&args("--json")));
+        // O valor de --physical não é confundido com o comando.
+        assert!(!is_cli(&args("--physical list")));
+    }
+
+    #[test]
+    fn parses_reset_options() {
+        let (options, positional) = parse_args(&args("--yes --only factory,color reset 1")).ok().unwrap();
+        assert!(options.yes);
+        assert_eq!(options.only, Some(vec![ResetCategory::Factory, ResetCategory::Color]));
+        assert_eq!(positional, ["reset", "1"]);
+
+        assert!(parse_args(&args("reset 1 --only")).is_err());
+        assert!(parse_args(&args("reset 1 --only hue")).is_err());
+    }
+}
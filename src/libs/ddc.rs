@@ -0,0 +1,468 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use windows::core::{HRESULT, Result};
+
+use super::i18n::t;
+use super::vcp::{
+    AUTO_SETUP,
+    INPUT_SOURCE,
+    POWER_MODE,
+    RESTORE_COLOR,
+    RESTORE_FACTORY_DEFAULTS,
+    RESTORE_GEOMETRY,
+    RESTORE_LUMINANCE_CONTRAST,
+    VcpKind,
+    VcpValue,
+};
+
+// Trocar a entrada ou desligar o monitor costuma deixá-lo sem responder à leitura seguinte.
+// Resets e auto-ajuste são momentâneos e ocupam o monitor por alguns segundos.
+const UNVERIFIED_CODES: [u8; 7] = [
+    INPUT_SOURCE,
+    POWER_MODE,
+    AUTO_SETUP,
+    RESTORE_FACTORY_DEFAULTS,
+    RESTORE_LUMINANCE_CONTRAST,
+    RESTORE_GEOMETRY,
+    RESTORE_COLOR,
+];
+
+pub trait Transport {
+    fn get(&mut self, code: u8) -> Result<VcpValue>;
+    fn set(&mut self, code: u8, value: u32) -> Result<()>;
+    fn capabilities(&mut self) -> Result<String>;
+
+    fn sleep(&mut self, duration: Duration) {
+        thread::sleep(duration);
+    }
+
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Quirk {
+    pub model: String,
+    pub extra_delay_ms: u64,
+    pub broken_codes: Vec<u8>,
+    pub max_overrides: Vec<(u8, u32)>,
+    pub unverified_codes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DdcConfig {
+    pub retries: u32,
+    pub retry_delay_ms: u64,
+    pub min_spacing_ms: u64,
+    pub verify_writes: bool,
+    pub auto_setup_ms: u64,
+    pub quirks: Vec<Quirk>,
+}
+
+impl Default for DdcConfig {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            retry_delay_ms: 100,
+            min_spacing_ms: 50,
+            verify_writes: true,
+            auto_setup_ms: 3000,
+            quirks: Vec::new(),
+        }
+    }
+}
+
+impl DdcConfig {
+    pub fn find_quirk(&self, model: &str) -> Option<&Quirk> {
+        let model = model.trim().to_ascii_uppercase();
+        self.quirks
+            .iter()
+            .find(|q| !model.is_empty() && !q.model.is_empty() && model.starts_with(&q.model.to_ascii_uppercase()))
+    }
+}
+
+pub struct DdcChannel<T: Transport> {
+    transport: T,
+    config: DdcConfig,
+    quirk: Option<Quirk>,
+    last_command: Option<Instant>,
+}
+
+impl<T: Transport> DdcChannel<T> {
+    pub fn new(transport: T, config: DdcConfig, model: &str, last_command: Option<Instant>) -> Self {
+        let quirk = config.find_quirk(model).cloned();
+        Self { transport, config, quirk, last_command }
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    pub fn last_command(&self) -> Option<Instant> {
+        self.last_command
+    }
+
+    pub fn get(&mut self, code: u8) -> Result<VcpValue> {
+        self.check_code(code)?;
+
+        let mut value = self.retry(|channel| channel.command(|t| t.get(code)))?;
+        if let Some((_, max)) = self.quirk.as_ref().and_then(|q| q.max_overrides.iter().find(|(c, _)| *c == code)) {
+            value.max = *max;
+        }
+
+        Ok(value)
+    }
+
+    pub fn set(&mut self, code: u8, value: u32) -> Result<()> {
+        self.check_code(code)?;
+
+        let verify = self.config.verify_writes
+            && !UNVERIFIED_CODES.contains(&code)
+            && !self.quirk.as_ref().is_some_and(|q| q.unverified_codes.contains(&code));
+
+        self.retry(|channel| channel.command(|t| t.set(code, value)))?;
+        if !verify {
+            return Ok(());
+        }
+
+        // Uma leitura divergente é repetida sem escrever de novo: o monitor pode
+        // ainda estar aplicando o valor, e reescrever só recomeçaria a transição.
+        self.retry(|channel| {
+            let read = channel.command(|t| t.get(code))?;
+            if read.kind == VcpKind::SetParameter && read.current != value {
+                return Err(windows::core::Error::new(
+                    HRESULT::from_win32(0x1F),
+                    t!("ddc-write-not-confirmed", code = format!("0x{:02X}", code), written = value, read = read.current),
+                ));
+            }
+            Ok(())
+        })
+    }
+
+    pub fn capabilities(&mut self) -> Result<String> {
+        self.retry(|channel| channel.command(|t| t.capabilities()))
+    }
+
+    fn check_code(&self, code: u8) -> Result<()> {
+        match &self.quirk {
+            Some(quirk) if quirk.broken_codes.contains(&code) => Err(windows::core::Error::new(
+                HRESULT::from_win32(0x32),
+                t!("ddc-code-disabled", code = format!("0x{:02X}", code), model = quirk.model.as_str()),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn retry<R>(&mut self, mut op: impl FnMut(&mut Self) -> Result<R>) -> Result<R> {
+        let mut attempt = 0;
+        loop {
+            match op(self) {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt >= self.config.retries => return Err(e),
+                Err(_) => {
+                    attempt += 1;
+                    let delay = Duration::from_millis(self.config.retry_delay_ms);
+                    self.transport.sleep(delay);
+                }
+            }
+        }
+    }
+
+    fn command<R>(&mut self, f: impl FnOnce(&mut T) -> Result<R>) -> Result<R> {
+        let extra = self.quirk.as_ref().map_or(0, |q| q.extra_delay_ms);
+        let spacing = Duration::from_millis(self.config.min_spacing_ms + extra);
+
+        if let Some(last) = self.last_command {
+            let elapsed = self.transport.now().saturating_duration_since(last);
+            if elapsed < spacing {
+                self.transport.sleep(spacing - elapsed);
+            }
+        }
+
+        let result = f(&mut self.transport);
+        self.last_command = Some(self.transport.now());
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::libs::vcp::{BRIGHTNESS, CONTRAST};
+
+    // Monitor simulado com relógio próprio: sleep() só avança o tempo.
+    struct FakeTransport {
+        start: Instant,
+        elapsed: Duration,
+        latency: Duration,
+        values: HashMap<u8, u32>,
+        // Próximas operações que falham antes de chegar ao monitor.
+        failing_gets: u32,
+        failing_sets: u32,
+        // Leituras que ainda devolvem o valor antigo depois de uma escrita.
+        stale_reads: u32,
+        ignore_writes: bool,
+        gets: u32,
+        sets: u32,
+        sleeps: Vec<Duration>,
+        // Instante de cada operação que chegou ao monitor.
+        commands: Vec<Duration>,
+    }
+
+    impl FakeTransport {
+        fn new() -> Self {
+            Self {
+                start: Instant::now(),
+                elapsed: Duration::ZERO,
+                latency: Duration::ZERO,
+                values: HashMap::from([(BRIGHTNESS, 50), (CONTRAST, 70)]),
+                failing_gets: 0,
+                failing_sets: 0,
+                stale_reads: 0,
+                ignore_writes: false,
+                gets: 0,
+                sets: 0,
+                sleeps: Vec::new(),
+                commands: Vec::new(),
+            }
+        }
+
+        fn operate(&mut self) {
+            self.commands.push(self.elapsed);
+            self.elapsed += self.latency;
+        }
+    }
+
+    fn failure() -> windows::core::Error {
+        windows::core::Error::new(HRESULT::from_win32(0x1F), "falha simulada")
+    }
+
+    impl Transport for FakeTransport {
+        fn get(&mut self, code: u8) -> Result<VcpValue> {
+            self.operate();
+            self.gets += 1;
+            if self.failing_gets > 0 {
+                self.failing_gets -= 1;
+                return Err(failure());
+            }
+            let mut current = self.values.get(&code).copied().unwrap_or(0);
+            if self.stale_reads > 0 {
+                self.stale_reads -= 1;
+                current = current.wrapping_add(1);
+            }
+            Ok(VcpValue { current, max: 100, kind: VcpKind::SetParameter })
+        }
+
+        fn set(&mut self, code: u8, value: u32) -> Result<()> {
+            self.operate();
+            self.sets += 1;
+            if self.failing_sets > 0 {
+                self.failing_sets -= 1;
+                return Err(failure());
+            }
+            if !self.ignore_writes {
+                self.values.insert(code, value);
+            }
+            Ok(())
+        }
+
+        fn capabilities(&mut self) -> Result<String> {
+            self.operate();
+            Ok("(prot(monitor))".to_string())
+        }
+
+        fn sleep(&mut self, duration: Duration) {
+            self.sleeps.push(duration);
+            self.elapsed += duration;
+        }
+
+        fn now(&self) -> Instant {
+            self.start + self.elapsed
+        }
+    }
+
+    fn channel(transport: FakeTransport, config: DdcConfig) -> DdcChannel<FakeTransport> {
+        DdcChannel::new(transport, config, "TEST 24", None)
+    }
+
+    fn quirk() -> Quirk {
+        Quirk {
+            model: "test".to_string(),
+            extra_delay_ms: 30,
+            broken_codes: vec![CONTRAST],
+            max_overrides: vec![(BRIGHTNESS, 80)],
+            unverified_codes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn retries_until_success() {
+        let mut transport = FakeTransport::new();
+        transport.failing_gets = 2;
+        let mut channel = channel(transport, DdcConfig::default());
+
+        assert_eq!(channel.get(BRIGHTNESS).unwrap().current, 50);
+        assert_eq!(channel.transport().gets, 3);
+    }
+
+    #[test]
+    fn gives_up_after_retries() {
+        let mut transport = FakeTransport::new();
+        transport.failing_sets = 10;
+        let config = DdcConfig { retries: 2, ..DdcConfig::default() };
+        let mut channel = channel(transport, config);
+
+        assert!(channel.set(BRIGHTNESS, 30).is_err());
+        let transport = channel.transport();
+        assert_eq!(transport.sets, 3);
+        assert_eq!(transport.gets, 0);
+        assert_eq!(transport.sleeps.iter().filter(|d| **d == Duration::from_millis(100)).count(), 2);
+    }
+
+    #[test]
+    fn verification_rereads_without_rewriting() {
+        let mut transport = FakeTransport::new();
+        transport.stale_reads = 2;
+        let mut channel = channel(transport, DdcConfig::default());
+
+        channel.set(BRIGHTNESS, 30).unwrap();
+        let transport = channel.transport();
+        assert_eq!(transport.sets, 1);
+        assert_eq!(transport.gets, 3);
+    }
+
+    #[test]
+    fn verification_mismatch_fails() {
+        let mut transport = FakeTransport::new();
+        transport.ignore_writes = true;
+        let mut channel = channel(transport, DdcConfig::default());
+
+        assert!(channel.set(BRIGHTNESS, 30).is_err());
+        let transport = channel.transport();
+        assert_eq!(transport.sets, 1);
+        assert_eq!(transport.gets, 4);
+    }
+
+    #[test]
+    fn failed_write_does_not_verify() {
+        let mut transport = FakeTransport::new();
+        transport.failing_sets = 1;
+        let mut channel = channel(transport, DdcConfig::default());
+
+        channel.set(BRIGHTNESS, 30).unwrap();
+        let transport = channel.transport();
+        assert_eq!(transport.sets, 2);
+        assert_eq!(transport.gets, 1);
+    }
+
+    #[test]
+    fn momentary_codes_are_not_verified() {
+        for code in [AUTO_SETUP, RESTORE_FACTORY_DEFAULTS, RESTORE_LUMINANCE_CONTRAST, RESTORE_GEOMETRY, RESTORE_COLOR, INPUT_SOURCE, POWER_MODE] {
+            let mut transport = FakeTransport::new();
+            transport.ignore_writes = true;
+            let mut channel = channel(transport, DdcConfig::default());
+
+            channel.set(code, 1).unwrap();
+            assert_eq!(channel.transport().gets, 0, "código 0x{code:02X}");
+        }
+    }
+
+    #[test]
+    fn quirk_unverified_codes_and_disabled_verification() {
+        let mut transport = FakeTransport::new();
+        transport.ignore_writes = true;
+        let config = DdcConfig {
+            quirks: vec![Quirk { unverified_codes: vec![BRIGHTNESS], ..quirk() }],
+            ..DdcConfig::default()
+        };
+        let mut channel = channel(transport, config);
+        channel.set(BRIGHTNESS, 30).unwrap();
+        assert_eq!(channel.transport().gets, 0);
+
+        let mut transport = FakeTransport::new();
+        transport.ignore_writes = true;
+        let config = DdcConfig { verify_writes: false, ..DdcConfig::default() };
+        let mut channel = self::channel(transport, config);
+        channel.set(BRIGHTNESS, 30).unwrap();
+        assert_eq!(channel.transport().gets, 0);
+    }
+
+    #[test]
+    fn broken_codes_never_reach_the_monitor() {
+        let config = DdcConfig { quirks: vec![quirk()], ..DdcConfig::default() };
+        let mut channel = channel(FakeTransport::new(), config);
+
+        assert!(channel.get(CONTRAST).is_err());
+        assert!(channel.set(CONTRAST, 10).is_err());
+        assert!(channel.transport().commands.is_empty());
+
+        // O quirk só vale para o modelo que casa com o prefixo.
+        let config = DdcConfig { quirks: vec![quirk()], ..DdcConfig::default() };
+        let mut channel = DdcChannel::new(FakeTransport::new(), config, "OTHER 27", None);
+        assert_eq!(channel.get(CONTRAST).unwrap().current, 70);
+    }
+
+    #[test]
+    fn quirk_overrides_max() {
+        let config = DdcConfig { quirks: vec![quirk()], ..DdcConfig::default() };
+        let mut channel = channel(FakeTransport::new(), config);
+
+        assert_eq!(channel.get(BRIGHTNESS).unwrap().max, 80);
+    }
+
+    #[test]
+    fn commands_are_spaced() {
+        let mut channel = channel(FakeTransport::new(), DdcConfig::default());
+
+        channel.get(BRIGHTNESS).unwrap();
+        channel.get(CONTRAST).unwrap();
+        channel.capabilities().unwrap();
+        let commands = &channel.transport().commands;
+        assert_eq!(commands.len(), 3);
+        for pair in commands.windows(2) {
+            assert_eq!(pair[1] - pair[0], Duration::from_millis(50));
+        }
+    }
+
+    #[test]
+    fn spacing_counts_from_end_of_command() {
+        // O intervalo vale depois da resposta, por mais que o comando tenha demorado.
+        let mut transport = FakeTransport::new();
+        transport.latency = Duration::from_millis(60);
+        let mut channel = channel(transport, DdcConfig::default());
+
+        channel.get(BRIGHTNESS).unwrap();
+        channel.get(BRIGHTNESS).unwrap();
+        let transport = channel.transport();
+        assert_eq!(transport.sleeps, [Duration::from_millis(50)]);
+        assert_eq!(transport.commands[1] - transport.commands[0], Duration::from_millis(110));
+    }
+
+    #[test]
+    fn quirk_delay_adds_to_spacing() {
+        let config = DdcConfig { quirks: vec![Quirk { broken_codes: Vec::new(), ..quirk() }], ..DdcConfig::default() };
+        let mut channel = channel(FakeTransport::new(), config);
+
+        channel.get(BRIGHTNESS).unwrap();
+        channel.get(CONTRAST).unwrap();
+        assert_eq!(channel.transport().sleeps, [Duration::from_millis(80)]);
+    }
+
+    #[test]
+    fn spacing_carries_over_from_previous_channel() {
+        let transport = FakeTransport::new();
+        let last = transport.now() - Duration::from_millis(20);
+        let mut channel = DdcChannel::new(transport, DdcConfig::default(), "TEST 24", Some(last));
+
+        channel.get(BRIGHTNESS).unwrap();
+        assert_eq!(channel.transport().sleeps, [Duration::from_millis(30)]);
+        assert_eq!(channel.last_command(), Some(channel.transport().now()));
+    }
+}
@@ -0,0 +1,987 @@
+use std::fmt;
+
+use super::i18n::t;
+
+const BLOCK: usize = 128;
+const HEADER: [u8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+
+const CTA_EXTENSION: u8 = 0x02;
+const DISPLAYID_EXTENSION: u8 = 0x70;
+
+const ESTABLISHED_TIMINGS: [(usize, u8, Timing); 17] = [
+    (35, 7, Timing::new(720, 400, 70)),
+    (35, 6, Timing::new(720, 400, 88)),
+    (35, 5, Timing::new(640, 480, 60)),
+    (35, 4, Timing::new(640, 480, 67)),
+    (35, 3, Timing::new(640, 480, 72)),
+    (35, 2, Timing::new(640, 480, 75)),
+    (35, 1, Timing::new(800, 600, 56)),
+    (35, 0, Timing::new(800, 600, 60)),
+    (36, 7, Timing::new(800, 600, 72)),
+    (36, 6, Timing::new(800, 600, 75)),
+    (36, 5, Timing::new(832, 624, 75)),
+    (36, 4, Timing::new(1024, 768, 87)),
+    (36, 3, Timing::new(1024, 768, 60)),
+    (36, 2, Timing::new(1024, 768, 70)),
+    (36, 1, Timing::new(1024, 768, 75)),
+    (36, 0, Timing::new(1280, 1024, 75)),
+    (37, 7, Timing::new(1152, 870, 75)),
+];
+
+// Subconjunto dos VICs do CTA-861 que aparecem na prática em monitores de PC e TVs.
+const VIC_TIMINGS: [(u8, Timing); 24] = [
+    (1, Timing::new(640, 480, 60)),
+    (2, Timing::new(720, 480, 60)),
+    (3, Timing::new(720, 480, 60)),
+    (4, Timing::new(1280, 720, 60)),
+    (5, Timing::new(1920, 1080, 60)),
+    (16, Timing::new(1920, 1080, 60)),
+    (17, Timing::new(720, 576, 50)),
+    (18, Timing::new(720, 576, 50)),
+    (19, Timing::new(1280, 720, 50)),
+    (20, Timing::new(1920, 1080, 50)),
+    (31, Timing::new(1920, 1080, 50)),
+    (32, Timing::new(1920, 1080, 24)),
+    (33, Timing::new(1920, 1080, 25)),
+    (34, Timing::new(1920, 1080, 30)),
+    (63, Timing::new(1920, 1080, 120)),
+    (64, Timing::new(1920, 1080, 100)),
+    (93, Timing::new(3840, 2160, 24)),
+    (94, Timing::new(3840, 2160, 25)),
+    (95, Timing::new(3840, 2160, 30)),
+    (96, Timing::new(3840, 2160, 50)),
+    (97, Timing::new(3840, 2160, 60)),
+    (98, Timing::new(4096, 2160, 24)),
+    (101, Timing::new(4096, 2160, 50)),
+    (102, Timing::new(4096, 2160, 60)),
+];
+
+// Identificadores PNP dos fabricantes mais comuns.
+const MANUFACTURERS: [(&str, &str); 20] = [
+    ("ACR", "Acer"),
+    ("AOC", "AOC"),
+    ("APP", "Apple"),
+    ("AUS", "ASUS"),
+    ("BNQ", "BenQ"),
+    ("DEL", "Dell"),
+    ("EIZ", "EIZO"),
+    ("GBT", "Gigabyte"),
+    ("GSM", "LG"),
+    ("HPN", "HP"),
+    ("HWP", "HP"),
+    ("IVM", "iiyama"),
+    ("LEN", "Lenovo"),
+    ("MSI", "MSI"),
+    ("NEC", "NEC"),
+    ("PHL", "Philips"),
+    ("SAM", "Samsung"),
+    ("SNY", "Sony"),
+    ("SEC", "Samsung"),
+    ("VSC", "ViewSonic"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Timing {
+    pub width: u32,
+    pub height: u32,
+    pub refresh: u32,
+}
+
+impl Timing {
+    const fn new(width: u32, height: u32, refresh: u32) -> Self {
+        Self { width, height, refresh }
+    }
+}
+
+impl fmt::Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{} @ {} Hz", self.width, self.height, self.refresh)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DetailedTiming {
+    pub pixel_clock_khz: u32,
+    pub width: u32,
+    pub height: u32,
+    pub refresh: f32,
+    pub interlaced: bool,
+    pub size_mm: Option<(u32, u32)>,
+}
+
+impl fmt::Display for DetailedTiming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}{} @ {:.2} Hz", self.width, self.height, if self.interlaced { "i" } else { "" }, self.refresh)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoInput {
+    Analog,
+    Digital { bit_depth: Option<u8>, interface: Option<&'static str> },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Chromaticity {
+    pub red: (f32, f32),
+    pub green: (f32, f32),
+    pub blue: (f32, f32),
+    pub white: (f32, f32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RangeLimits {
+    pub min_vertical_hz: u32,
+    pub max_vertical_hz: u32,
+    pub min_horizontal_khz: u32,
+    pub max_horizontal_khz: u32,
+    pub max_pixel_clock_mhz: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HdrMetadata {
+    pub eotfs: Vec<&'static str>,
+    pub max_luminance: Option<f32>,
+    pub max_frame_average: Option<f32>,
+    pub min_luminance: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edid {
+    pub version: (u8, u8),
+    pub manufacturer: String,
+    pub product_code: u16,
+    pub serial: Option<u32>,
+    pub serial_text: Option<String>,
+    pub name: Option<String>,
+    pub week: Option<u8>,
+    pub year: u16,
+    pub input: VideoInput,
+    pub size_cm: Option<(u8, u8)>,
+    pub gamma: Option<f32>,
+    pub chromaticity: Chromaticity,
+    pub established: Vec<Timing>,
+    pub standard: Vec<Timing>,
+    pub detailed: Vec<DetailedTiming>,
+    pub range_limits: Option<RangeLimits>,
+    pub vics: Vec<u8>,
+    pub native_vics: Vec<u8>,
+    pub colorimetry: Vec<&'static str>,
+    pub hdr: Option<HdrMetadata>,
+    pub hdmi: bool,
+    pub extensions: Vec<u8>,
+    pub checksum_valid: bool,
+}
+
+impl Edid {
+    pub fn preferred(&self) -> Option<&DetailedTiming> {
+        self.detailed.first()
+    }
+
+    // Fabricante, produto e série: não muda com a porta nem com a ordem de enumeração.
+    pub fn identity(&self) -> String {
+        let serial = self.serial_text.clone().or(self.serial.map(|s| s.to_string()));
+        match serial {
+            Some(serial) => format!("{}-{:04X}-{}", self.manufacturer, self.product_code, serial),
+            None => format!("{}-{:04X}", self.manufacturer, self.product_code),
+        }
+    }
+
+    pub fn diagonal_inches(&self) -> Option<f32> {
+        let (w, h) = self.size_cm?;
+        Some(((w as f32).powi(2) + (h as f32).powi(2)).sqrt() / 2.54)
+    }
+
+    // Todos os modos anunciados, sem repetição, do maior para o menor.
+    pub fn supported_timings(&self) -> Vec<Timing> {
+        let mut timings: Vec<Timing> = self.detailed
+            .iter()
+            .map(|d| Timing::new(d.width, d.height, d.refresh.round() as u32))
+            .chain(self.established.iter().copied())
+            .chain(self.standard.iter().copied())
+            .chain(self.vics.iter().filter_map(|&vic| vic_timing(vic)))
+            .collect();
+        timings.sort_by_key(|t| std::cmp::Reverse((t.width as u64 * t.height as u64, t.refresh)));
+        timings.dedup();
+        timings
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EdidError {
+    TooShort(usize),
+    InvalidHeader,
+}
+
+impl fmt::Display for EdidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EdidError::TooShort(len) => write!(f, "{}", t!("edid-too-short", len = *len, min = BLOCK)),
+            EdidError::InvalidHeader => write!(f, "{}", t!("edid-invalid-header")),
+        }
+    }
+}
+
+impl std::error::Error for EdidError {}
+
+pub fn manufacturer_name(id: &str) -> &str {
+    MANUFACTURERS.iter().find(|(pnp, _)| *pnp == id).map_or(id, |(_, name)| *name)
+}
+
+fn vic_timing(vic: u8) -> Option<Timing> {
+    VIC_TIMINGS.iter().find(|(v, _)| *v == vic).map(|(_, t)| *t)
+}
+
+pub fn parse_edid(data: &[u8]) -> Result<Edid, EdidError> {
+    if data.len() < BLOCK {
+        return Err(EdidError::TooShort(data.len()));
+    }
+    let base = &data[..BLOCK];
+    if base[..8] != HEADER {
+        return Err(EdidError::InvalidHeader);
+    }
+
+    let letter = |bits: u16| match (bits & 0x1F) as u8 {
+        c @ 1..=26 => (b'A' + c - 1) as char,
+        _ => '?',
+    };
+    let id = u16::from_be_bytes([base[8], base[9]]);
+    let manufacturer = [letter(id >> 10), letter(id >> 5), letter(id)].iter().collect();
+
+    let input = if base[20] & 0x80 != 0 {
+        let bit_depth = match (base[20] >> 4) & 0x07 {
+            depth @ 1..=6 => Some(4 + depth * 2),
+            _ => None,
+        };
+        let interface = match base[20] & 0x0F {
+            1 => Some("DVI"),
+            2 | 3 => Some("HDMI"),
+            4 => Some("MDDI"),
+            5 => Some("DisplayPort"),
+            _ => None,
+        };
+        VideoInput::Digital { bit_depth, interface }
+    } else {
+        VideoInput::Analog
+    };
+
+    let mut edid = Edid {
+        version: (base[18], base[19]),
+        manufacturer,
+        product_code: u16::from_le_bytes([base[10], base[11]]),
+        serial: Some(u32::from_le_bytes([base[12], base[13], base[14], base[15]])).filter(|s| *s != 0),
+        serial_text: None,
+        name: None,
+        // Semana 0xFF indica que o ano é o do modelo, não o de fabricação.
+        week: Some(base[16]).filter(|w| (1..=54).contains(w)),
+        year: 1990 + base[17] as u16,
+        input,
+        size_cm: Some((base[21], base[22])).filter(|(w, h)| *w != 0 && *h != 0),
+        gamma: Some(base[23]).filter(|g| *g != 0xFF).map(|g| (g as f32 + 100.0) / 100.0),
+        chromaticity: parse_chromaticity(base),
+        established: ESTABLISHED_TIMINGS
+            .iter()
+            .filter(|(byte, bit, _)| base[*byte] & (1 << bit) != 0)
+            .map(|(_, _, timing)| *timing)
+            .collect(),
+        standard: base[38..54]
+            .chunks_exact(2)
+            .filter_map(|pair| parse_standard_timing(pair[0], pair[1], base[19]))
+            .collect(),
+        detailed: Vec::new(),
+        range_limits: None,
+        vics: Vec::new(),
+        native_vics: Vec::new(),
+        colorimetry: Vec::new(),
+        hdr: None,
+        hdmi: false,
+        extensions: Vec::new(),
+        checksum_valid: checksum(base),
+    };
+
+    for descriptor in base[54..126].chunks_exact(18) {
+        parse_descriptor(&mut edid, descriptor);
+    }
+
+    // O contador de extensões do bloco base pode mentir; vale o que foi realmente lido.
+    for block in data[BLOCK..].chunks_exact(BLOCK) {
+        edid.extensions.push(block[0]);
+        edid.checksum_valid &= checksum(block);
+        match block[0] {
+            CTA_EXTENSION => parse_cta(&mut edid, block),
+            DISPLAYID_EXTENSION => parse_displayid(&mut edid, block),
+            _ => {}
+        }
+    }
+
+    Ok(edid)
+}
+
+fn checksum(block: &[u8]) -> bool {
+    block.iter().fold(0u8, |sum, b| sum.wrapping_add(*b)) == 0
+}
+
+fn parse_chromaticity(base: &[u8]) -> Chromaticity {
+    let coord = |high: u8, low: u8, shift: u8| (((high as u16) << 2) | ((low >> shift) & 0x03) as u16) as f32 / 1024.0;
+    Chromaticity {
+        red: (coord(base[27], base[25], 6), coord(base[28], base[25], 4)),
+        green: (coord(base[29], base[25], 2), coord(base[30], base[25], 0)),
+        blue: (coord(base[31], base[26], 6), coord(base[32], base[26], 4)),
+        white: (coord(base[33], base[26], 2), coord(base[34], base[26], 0)),
+    }
+}
+
+fn parse_standard_timing(first: u8, second: u8, revision: u8) -> Option<Timing> {
+    if first <= 0x01 {
+        return None;
+    }
+
+    let width = (first as u32 + 31) * 8;
+    let height = match second >> 6 {
+        0 if revision < 3 => width,
+        0 => width * 10 / 16,
+        1 => width * 3 / 4,
+        2 => width * 4 / 5,
+        _ => width * 9 / 16,
+    };
+    Some(Timing::new(width, height, (second & 0x3F) as u32 + 60))
+}
+
+fn parse_detailed_timing(d: &[u8], clock_unit_khz: u32) -> Option<DetailedTiming> {
+    let pixel_clock_khz = u16::from_le_bytes([d[0], d[1]]) as u32 * clock_unit_khz;
+    let width = d[2] as u32 | ((d[4] as u32 >> 4) << 8);
+    let h_blank = d[3] as u32 | ((d[4] as u32 & 0x0F) << 8);
+    let height = d[5] as u32 | ((d[7] as u32 >> 4) << 8);
+    let v_blank = d[6] as u32 | ((d[7] as u32 & 0x0F) << 8);
+    let size = (d[12] as u32 | ((d[14] as u32 >> 4) << 8), d[13] as u32 | ((d[14] as u32 & 0x0F) << 8));
+
+    detailed_timing(pixel_clock_khz, width, height, h_blank, v_blank, d[17] & 0x80 != 0, size)
+}
+
+fn detailed_timing(
+    pixel_clock_khz: u32,
+    width: u32,
+    height: u32,
+    h_blank: u32,
+    v_blank: u32,
+    interlaced: bool,
+    size_mm: (u32, u32),
+) -> Option<DetailedTiming> {
+    let total = (width + h_blank) as f64 * (height + v_blank) as f64;
+    if pixel_clock_khz == 0 || width == 0 || height == 0 || total == 0.0 {
+        return None;
+    }
+
+    Some(DetailedTiming {
+        pixel_clock_khz,
+        width,
+        height,
+        refresh: (pixel_clock_khz as f64 * 1000.0 / total) as f32,
+        interlaced,
+        size_mm: Some(size_mm).filter(|(w, h)| *w != 0 && *h != 0),
+    })
+}
+
+fn descriptor_text(d: &[u8]) -> Option<String> {
+    let text: String = d[5..18]
+        .iter()
+        .take_while(|b| **b != 0x0A && **b != 0x00)
+        .map(|b| if b.is_ascii_graphic() || *b == b' ' { *b as char } else { '?' })
+        .collect();
+    Some(text.trim().to_string()).filter(|t| !t.is_empty())
+}
+
+fn parse_descriptor(edid: &mut Edid, d: &[u8]) {
+    if d[0] != 0 || d[1] != 0 {
+        edid.detailed.extend(parse_detailed_timing(d, 10));
+        return;
+    }
+
+    match d[3] {
+        0xFF => edid.serial_text = descriptor_text(d),
+        0xFC => edid.name = descriptor_text(d),
+        0xFD => {
+            // Na versão 1.4 os bits de deslocamento somam 255 aos limites.
+            let offset = |mask: u8| if d[4] & mask == mask { 255 } else { 0 };
+            edid.range_limits = Some(RangeLimits {
+                min_vertical_hz: d[5] as u32 + offset(0x03),
+                max_vertical_hz: d[6] as u32 + offset(0x02),
+                min_horizontal_khz: d[7] as u32 + offset(0x0C),
+                max_horizontal_khz: d[8] as u32 + offset(0x08),
+                max_pixel_clock_mhz: Some(d[9] as u32 * 10).filter(|c| *c != 0),
+            });
+        }
+        _ => {}
+    }
+}
+
+fn parse_cta(edid: &mut Edid, block: &[u8]) {
+    let dtd_offset = (block[2] as usize).clamp(4, 127);
+
+    let mut pos = 4;
+    while pos < dtd_offset {
+        let tag = block[pos] >> 5;
+        let len = (block[pos] & 0x1F) as usize;
+        let Some(payload) = block.get(pos + 1..(pos + 1 + len).min(dtd_offset)) else {
+            break;
+        };
+
+        match tag {
+            2 => {
+                for &svd in payload {
+                    let (vic, native) = match svd {
+                        1..=64 => (svd, false),
+                        129..=192 => (svd & 0x7F, true),
+                        _ => (svd, false),
+                    };
+                    if vic == 0 {
+                        continue;
+                    }
+                    edid.vics.push(vic);
+                    if native {
+                        edid.native_vics.push(vic);
+                    }
+                }
+            }
+            3 if payload.len() >= 3 => {
+                edid.hdmi |= payload[..3] == [0x03, 0x0C, 0x00];
+            }
+            7 if !payload.is_empty() => parse_cta_extended(edid, payload),
+            _ => {}
+        }
+
+        pos += 1 + len;
+    }
+
+    let mut pos = dtd_offset;
+    while pos + 18 <= 127 {
+        let d = &block[pos..pos + 18];
+        if d[0] == 0 && d[1] == 0 {
+            break;
+        }
+        edid.detailed.extend(parse_detailed_timing(d, 10));
+        pos += 18;
+    }
+}
+
+fn parse_cta_extended(edid: &mut Edid, payload: &[u8]) {
+    match payload[0] {
+        5 => {
+            const NAMES: [&str; 8] = ["xvYCC601", "xvYCC709", "sYCC601", "opYCC601", "opRGB", "BT2020cYCC", "BT2020YCC", "BT2020RGB"];
+            let bits = payload.get(1).copied().unwrap_or(0);
+            edid.colorimetry.extend(NAMES.iter().enumerate().filter(|(i, _)| bits & (1 << i) != 0).map(|(_, n)| *n));
+            if payload.get(2).is_some_and(|b| b & 0x80 != 0) {
+                edid.colorimetry.push("DCI-P3");
+            }
+        }
+        6 => {
+            const EOTFS: [&str; 4] = ["SDR", "HDR", "PQ (ST 2084)", "HLG"];
+            let bits = payload.get(1).copied().unwrap_or(0);
+            let luminance = |code: Option<&u8>| code.filter(|c| **c != 0).map(|c| 50.0 * 2f32.powf(*c as f32 / 32.0));
+            let max_luminance = luminance(payload.get(3));
+            edid.hdr = Some(HdrMetadata {
+                eotfs: EOTFS.iter().enumerate().filter(|(i, _)| bits & (1 << i) != 0).map(|(_, n)| *n).collect(),
+                max_luminance,
+                max_frame_average: luminance(payload.get(4)),
+                min_luminance: payload.get(5)
+                    .zip(max_luminance)
+                    .map(|(code, max)| max * (*code as f32 / 255.0).powi(2) / 100.0),
+            });
+        }
+        _ => {}
+    }
+}
+
+fn parse_displayid(edid: &mut Edid, block: &[u8]) {
+    let end = (5 + block[2] as usize).min(127);
+
+    let mut pos = 5;
+    while pos + 3 <= end {
+        let tag = block[pos];
+        let len = block[pos + 2] as usize;
+        let Some(payload) = block.get(pos + 3..pos + 3 + len).filter(|_| pos + 3 + len <= end) else {
+            break;
+        };
+
+        match tag {
+            // Bloco de identificação do produto: 1.x usa 0x00 e 2.x usa 0x20.
+            0x00 | 0x20 if payload.len() >= 12 => {
+                let name_len = payload[11] as usize;
+                if edid.name.is_none()
+                    && let Some(name) = payload.get(12..12 + name_len)
+                {
+                    let name = String::from_utf8_lossy(name).trim().to_string();
+                    edid.name = Some(name).filter(|n| !n.is_empty());
+                }
+            }
+            // Tipo I (1.x) conta o clock em 10 kHz; Tipo VII (2.x) em 1 kHz.
+            0x03 | 0x22 => {
+                let unit = if tag == 0x03 { 10 } else { 1 };
+                for d in payload.chunks_exact(20) {
+                    let field = |i: usize| u16::from_le_bytes([d[i], d[i + 1]]) as u32 + 1;
+                    let clock = (u32::from_le_bytes([d[0], d[1], d[2], 0]) + 1) * unit;
+                    edid.detailed.extend(detailed_timing(
+                        clock,
+                        field(4),
+                        field(12),
+                        field(6),
+                        field(14),
+                        d[3] & 0x10 != 0,
+                        (0, 0),
+                    ));
+                }
+            }
+            _ => {}
+        }
+
+        pos += 3 + len;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Montados byte a byte a partir das especificações VESA/CTA (ver testdata/edid/README.md).
+    const HDMI_4K: &[u8] = include_bytes!("../../testdata/edid/hdmi-4k-cta.bin");
+    const DP_8K: &[u8] = include_bytes!("../../testdata/edid/dp-8k-displayid13.bin");
+    const EDP_PANEL: &[u8] = include_bytes!("../../testdata/edid/edp-panel-displayid20.bin");
+    const VGA_ANALOG: &[u8] = include_bytes!("../../testdata/edid/vga-analog.bin");
+    const CORPUS: [&[u8]; 4] = [HDMI_4K, DP_8K, EDP_PANEL, VGA_ANALOG];
+
+    fn seal(block: &mut [u8]) {
+        let sum = block[..BLOCK - 1].iter().fold(0u8, |sum, b| sum.wrapping_add(*b));
+        block[BLOCK - 1] = sum.wrapping_neg();
+    }
+
+    fn text_descriptor(tag: u8, text: &str) -> [u8; 18] {
+        let mut d = [0u8; 18];
+        d[3] = tag;
+        d[5..].fill(b' ');
+        d[5..5 + text.len()].copy_from_slice(text.as_bytes());
+        if text.len() < 13 {
+            d[5 + text.len()] = 0x0A;
+        }
+        d
+    }
+
+    // Bloco base de um Dell 27" com 1920x1080 @ 60 Hz preferido.
+    fn base_block() -> Vec<u8> {
+        let mut b = vec![0u8; BLOCK];
+        b[..8].copy_from_slice(&HEADER);
+        b[8..10].copy_from_slice(&0x10ACu16.to_be_bytes());
+        b[10..12].copy_from_slice(&0xA0A5u16.to_le_bytes());
+        b[12..16].copy_from_slice(&0x1234_5678u32.to_le_bytes());
+        b[16] = 10;
+        b[17] = 30;
+        b[18] = 1;
+        b[19] = 4;
+        b[20] = 0xA5;
+        b[21] = 60;
+        b[22] = 34;
+        b[23] = 120;
+        b[35] = 0x20;
+        b[38..54].fill(0x01);
+        b[38] = 0xD1;
+        b[39] = 0xC0;
+        b[54..72].copy_from_slice(&[
+            0x02, 0x3A, 0x80, 0x18, 0x71, 0x38, 0x2D, 0x40, 0x58, 0x2C, 0x45, 0x00, 0x13, 0x2B, 0x21, 0x00, 0x00, 0x1E,
+        ]);
+        b[72..90].copy_from_slice(&text_descriptor(0xFC, "DELL U2720Q"));
+        b[90..108].copy_from_slice(&text_descriptor(0xFF, "ABC123"));
+        b[108..126].copy_from_slice(&[0, 0, 0, 0xFD, 0, 48, 75, 30, 140, 60, 0, 0x0A, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20]);
+        seal(&mut b);
+        b
+    }
+
+    fn cta_block() -> Vec<u8> {
+        let mut b = vec![0u8; BLOCK];
+        b[0] = CTA_EXTENSION;
+        b[1] = 3;
+        b[2] = 21;
+        let blocks: [&[u8]; 3] = [
+            // VICs 16 (nativo), 4 e 97.
+            &[0x43, 0x90, 0x04, 0x61],
+            // VSDB da HDMI.
+            &[0x65, 0x03, 0x0C, 0x00, 0x10, 0x00],
+            // Metadados HDR estáticos: SDR e PQ, 400 nits.
+            &[0xE6, 0x06, 0x05, 0x01, 0x60, 0x50, 0x20],
+        ];
+        let mut pos = 4;
+        for data in blocks {
+            b[pos..pos + data.len()].copy_from_slice(data);
+            pos += data.len();
+        }
+        b[21..39].copy_from_slice(&[
+            0x01, 0x1D, 0x00, 0x72, 0x51, 0xD0, 0x1E, 0x20, 0x6E, 0x28, 0x55, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x18,
+        ]);
+        seal(&mut b);
+        b
+    }
+
+    #[test]
+    fn parses_base_block() {
+        let edid = parse_edid(&base_block()).unwrap();
+        assert!(edid.checksum_valid);
+        assert_eq!(edid.version, (1, 4));
+        assert_eq!(edid.manufacturer, "DEL");
+        assert_eq!(manufacturer_name(&edid.manufacturer), "Dell");
+        assert_eq!(edid.product_code, 0xA0A5);
+        assert_eq!(edid.serial, Some(0x1234_5678));
+        assert_eq!(edid.serial_text.as_deref(), Some("ABC123"));
+        assert_eq!(edid.name.as_deref(), Some("DELL U2720Q"));
+        assert_eq!((edid.week, edid.year), (Some(10), 2020));
+        assert_eq!(edid.input, VideoInput::Digital { bit_depth: Some(8), interface: Some("DisplayPort") });
+        assert_eq!(edid.size_cm, Some((60, 34)));
+        assert_eq!(edid.gamma, Some(2.2));
+        assert_eq!(edid.established, [Timing::new(640, 480, 60)]);
+        assert_eq!(edid.standard, [Timing::new(1920, 1080, 60)]);
+        assert!(edid.extensions.is_empty());
+        assert_eq!(edid.identity(), "DEL-A0A5-ABC123");
+
+        let preferred = edid.preferred().unwrap();
+        assert_eq!((preferred.width, preferred.height, preferred.pixel_clock_khz), (1920, 1080, 148_500));
+        assert!((preferred.refresh - 60.0).abs() < 0.01);
+        assert_eq!(preferred.size_mm, Some((531, 299)));
+
+        let limits = edid.range_limits.unwrap();
+        assert_eq!((limits.min_vertical_hz, limits.max_vertical_hz), (48, 75));
+        assert_eq!(limits.max_pixel_clock_mhz, Some(600));
+    }
+
+    #[test]
+    fn identity_without_serial_text() {
+        let mut data = base_block();
+        data[90..108].copy_from_slice(&text_descriptor(0x10, ""));
+        seal(&mut data);
+        assert_eq!(parse_edid(&data).unwrap().identity(), "DEL-A0A5-305419896");
+
+        data[12..16].fill(0);
+        seal(&mut data);
+        assert_eq!(parse_edid(&data).unwrap().identity(), "DEL-A0A5");
+    }
+
+    #[test]
+    fn parses_cta_extension() {
+        let mut data = base_block();
+        data[126] = 1;
+        seal(&mut data);
+        data.extend(cta_block());
+
+        let edid = parse_edid(&data).unwrap();
+        assert!(edid.checksum_valid);
+        assert_eq!(edid.extensions, [CTA_EXTENSION]);
+        assert_eq!(edid.vics, [16, 4, 97]);
+        assert_eq!(edid.native_vics, [16]);
+        assert!(edid.hdmi);
+        let hdr = edid.hdr.as_ref().unwrap();
+        assert_eq!(hdr.eotfs, ["SDR", "PQ (ST 2084)"]);
+        assert_eq!(hdr.max_luminance, Some(400.0));
+        assert_eq!(edid.detailed.len(), 2);
+        assert_eq!((edid.detailed[1].width, edid.detailed[1].height), (1280, 720));
+
+        let timings = edid.supported_timings();
+        assert_eq!(timings.first(), Some(&Timing::new(3840, 2160, 60)));
+        assert_eq!(timings.iter().filter(|t| **t == Timing::new(1920, 1080, 60)).count(), 1);
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        assert_eq!(parse_edid(&[]), Err(EdidError::TooShort(0)));
+        assert_eq!(parse_edid(&base_block()[..100]), Err(EdidError::TooShort(100)));
+    }
+
+    #[test]
+    fn rejects_invalid_header() {
+        let mut data = base_block();
+        data[0] = 0xFF;
+        assert_eq!(parse_edid(&data), Err(EdidError::InvalidHeader));
+    }
+
+    #[test]
+    fn reports_bad_checksum() {
+        let mut data = base_block();
+        data[21] = 61;
+        let edid = parse_edid(&data).unwrap();
+        assert!(!edid.checksum_valid);
+        assert_eq!(edid.size_cm, Some((61, 34)));
+
+        // Uma extensão corrompida também invalida o conjunto.
+        let mut data = base_block();
+        let mut cta = cta_block();
+        cta[127] ^= 0xFF;
+        data.extend(cta);
+        assert!(!parse_edid(&data).unwrap().checksum_valid);
+    }
+
+    #[test]
+    fn ignores_partial_extension() {
+        let mut data = base_block();
+        data.extend(&cta_block()[..60]);
+        let edid = parse_edid(&data).unwrap();
+        assert!(edid.extensions.is_empty());
+        assert!(edid.checksum_valid);
+    }
+
+    #[test]
+    fn tolerates_garbage_descriptors() {
+        let mut data = base_block();
+        // Timing com clock mas sem largura, texto com bytes de controle e tipo desconhecido.
+        data[54..72].copy_from_slice(&[0x02, 0x3A, 0, 0, 0, 0x38, 0x2D, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        data[72..90].copy_from_slice(&[0, 0, 0, 0xFC, 0, b'A', 0x07, b'B', 0xFE, b'C', 0x0A, 0, 0, 0, 0, 0, 0, 0]);
+        data[90..108].copy_from_slice(&[0, 0, 0, 0x42, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+        data[108..126].fill(0xFF);
+        data[126] = 0xFF;
+        seal(&mut data);
+
+        let edid = parse_edid(&data).unwrap();
+        assert_eq!(edid.name.as_deref(), Some("A?B?C"));
+        assert_eq!(edid.serial_text, None);
+        assert_eq!(edid.range_limits, None);
+        // Só o último descritor, todo 0xFF, forma um timing (absurdo, mas bem formado).
+        assert_eq!(edid.detailed.len(), 1);
+        assert!(edid.extensions.is_empty());
+    }
+
+    fn modes(edid: &Edid) -> Vec<(u32, u32, u32)> {
+        edid.detailed.iter().map(|d| (d.width, d.height, d.refresh.round() as u32)).collect()
+    }
+
+    // Seção DisplayID com os blocos de dados dados, já com os dois checksums.
+    fn displayid_block(version: u8, blocks: &[&[u8]]) -> Vec<u8> {
+        let data = blocks.concat();
+        let mut b = vec![0u8; BLOCK];
+        b[0] = DISPLAYID_EXTENSION;
+        b[1] = version;
+        b[2] = data.len() as u8;
+        b[5..5 + data.len()].copy_from_slice(&data);
+        b[5 + data.len()] = b[1..5 + data.len()].iter().fold(0u8, |sum, v| sum.wrapping_add(*v)).wrapping_neg();
+        seal(&mut b);
+        b
+    }
+
+    fn product_block(tag: u8, name: &str) -> Vec<u8> {
+        let mut b = vec![tag, 0, (12 + name.len()) as u8, 0x00, 0x14, 0xB0, 0x37, 0x41, 0, 0, 0, 0, 1, 30, name.len() as u8];
+        b.extend(name.as_bytes());
+        b
+    }
+
+    fn timing_block(tag: u8, clock: u32, width: u16, height: u16, options: u8) -> Vec<u8> {
+        let mut b = vec![tag, 0, 20];
+        b.extend(&(clock - 1).to_le_bytes()[..3]);
+        b.push(options);
+        for field in [width, 80, 48, 32, height, 62, 3, 5] {
+            b.extend((field - 1).to_le_bytes());
+        }
+        b
+    }
+
+    #[test]
+    fn parses_hdmi_monitor_with_cta() {
+        let edid = parse_edid(HDMI_4K).unwrap();
+        assert!(edid.checksum_valid);
+        assert_eq!(edid.version, (1, 3));
+        assert_eq!((edid.manufacturer.as_str(), edid.product_code), ("GSM", 0x5B09));
+        assert_eq!(manufacturer_name(&edid.manufacturer), "LG");
+        assert_eq!(edid.name.as_deref(), Some("LG HDR 4K"));
+        assert_eq!((edid.serial, edid.serial_text.as_deref()), (Some(303_793), None));
+        assert_eq!(edid.identity(), "GSM-5B09-303793");
+        assert_eq!((edid.week, edid.year), (Some(12), 2019));
+        // Na 1.3 os bits de profundidade e interface não existem.
+        assert_eq!(edid.input, VideoInput::Digital { bit_depth: None, interface: None });
+        assert_eq!(edid.extensions, [CTA_EXTENSION]);
+
+        assert_eq!(modes(&edid), [(3840, 2160, 60), (1920, 1080, 60), (3840, 2160, 30)]);
+        assert_eq!(edid.established, [Timing::new(640, 480, 60), Timing::new(800, 600, 60), Timing::new(1024, 768, 60)]);
+        assert_eq!(
+            edid.standard,
+            [Timing::new(1920, 1080, 60), Timing::new(1280, 720, 60), Timing::new(1600, 900, 60), Timing::new(1680, 1050, 60)]
+        );
+        assert_eq!(edid.vics, [16, 4, 3, 31, 97, 95]);
+        assert_eq!(edid.native_vics, [16]);
+        assert!(edid.hdmi);
+        assert_eq!(edid.colorimetry, ["BT2020YCC", "BT2020RGB"]);
+        let hdr = edid.hdr.as_ref().unwrap();
+        assert_eq!(hdr.eotfs, ["SDR", "PQ (ST 2084)", "HLG"]);
+        assert!((hdr.max_luminance.unwrap() - 301.8).abs() < 0.1);
+
+        let timings = edid.supported_timings();
+        assert_eq!(&timings[..3], [Timing::new(3840, 2160, 60), Timing::new(3840, 2160, 30), Timing::new(1920, 1080, 60)]);
+        assert_eq!(timings.iter().filter(|t| **t == Timing::new(1920, 1080, 60)).count(), 1);
+    }
+
+    #[test]
+    fn parses_displayport_monitor_with_displayid_1() {
+        let edid = parse_edid(DP_8K).unwrap();
+        assert!(edid.checksum_valid);
+        assert_eq!(edid.version, (1, 4));
+        assert_eq!((edid.manufacturer.as_str(), edid.product_code), ("DEL", 0x4137));
+        // O nome do bloco base prevalece sobre o da DisplayID ("DELLUP3218K").
+        assert_eq!(edid.name.as_deref(), Some("DELL UP3218K"));
+        assert_eq!(edid.serial_text.as_deref(), Some("7MT0183R0CKL"));
+        assert_eq!(edid.identity(), "DEL-4137-7MT0183R0CKL");
+        assert_eq!((edid.week, edid.year), (Some(45), 2017));
+        assert_eq!(edid.input, VideoInput::Digital { bit_depth: Some(8), interface: Some("DisplayPort") });
+        assert_eq!(edid.extensions, [DISPLAYID_EXTENSION]);
+
+        // O 8K só existe nos timings Tipo I da extensão.
+        assert_eq!(modes(&edid), [(3840, 2160, 60), (7680, 4320, 60), (7680, 4320, 30)]);
+        assert_eq!(edid.detailed[1].pixel_clock_khz, 2_040_230);
+        assert_eq!(edid.detailed[1].size_mm, None);
+        assert!(!edid.detailed[1].interlaced);
+        assert_eq!(edid.supported_timings().first(), Some(&Timing::new(7680, 4320, 60)));
+    }
+
+    #[test]
+    fn parses_laptop_panel_with_displayid_2() {
+        let edid = parse_edid(EDP_PANEL).unwrap();
+        assert!(edid.checksum_valid);
+        assert_eq!((edid.manufacturer.as_str(), edid.product_code), ("BOE", 0x0A1C));
+        // Painéis costumam trazer só descritores 0xFE; o nome vem do bloco de produto da DisplayID.
+        assert_eq!(edid.name.as_deref(), Some("NE160QDM-NY1"));
+        assert_eq!((edid.serial, edid.serial_text.as_deref()), (None, None));
+        assert_eq!(edid.identity(), "BOE-0A1C");
+        assert_eq!((edid.week, edid.year), (None, 2022));
+        assert_eq!(edid.size_cm, Some((34, 22)));
+        assert!(edid.established.is_empty() && edid.standard.is_empty());
+
+        assert_eq!(modes(&edid), [(2560, 1600, 60), (2560, 1600, 165)]);
+        // Tipo VII conta o clock em kHz, não em 10 kHz.
+        assert_eq!(edid.detailed[1].pixel_clock_khz, 717_869);
+        assert_eq!(edid.supported_timings(), [Timing::new(2560, 1600, 165), Timing::new(2560, 1600, 60)]);
+    }
+
+    #[test]
+    fn parses_analog_monitor() {
+        let edid = parse_edid(VGA_ANALOG).unwrap();
+        assert!(edid.checksum_valid);
+        assert_eq!(edid.version, (1, 3));
+        assert_eq!((edid.manufacturer.as_str(), edid.product_code), ("SAM", 0x0215));
+        assert_eq!(edid.name.as_deref(), Some("SyncMaster"));
+        assert_eq!(edid.identity(), "SAM-0215-H9XS123456");
+        assert_eq!((edid.week, edid.year), (Some(23), 2008));
+        assert_eq!(edid.input, VideoInput::Analog);
+        assert!(edid.extensions.is_empty());
+
+        assert_eq!(modes(&edid), [(1280, 1024, 60)]);
+        assert_eq!(edid.established.len(), 14);
+        assert_eq!(
+            edid.standard,
+            [
+                Timing::new(1280, 1024, 60),
+                Timing::new(1280, 1024, 75),
+                Timing::new(1152, 864, 75),
+                Timing::new(1024, 768, 85),
+                Timing::new(800, 600, 85),
+                Timing::new(640, 480, 85),
+            ]
+        );
+        let limits = edid.range_limits.unwrap();
+        assert_eq!((limits.min_vertical_hz, limits.max_vertical_hz, limits.max_horizontal_khz), (56, 75, 81));
+        assert_eq!(edid.supported_timings().first(), Some(&Timing::new(1280, 1024, 75)));
+    }
+
+    #[test]
+    fn corpus_truncated_variants() {
+        for data in CORPUS {
+            let full = parse_edid(data).unwrap();
+            for len in [0, 8, 100, BLOCK - 1] {
+                assert_eq!(parse_edid(&data[..len]), Err(EdidError::TooShort(len)));
+            }
+
+            // Sem a extensão, ou com ela pela metade, sobra o bloco base intacto.
+            for len in [BLOCK, BLOCK + 40, data.len() - 1].into_iter().filter(|len| (BLOCK..data.len()).contains(len)) {
+                let edid = parse_edid(&data[..len]).unwrap();
+                assert!(edid.checksum_valid);
+                assert!(edid.extensions.is_empty());
+                assert_eq!(edid.identity(), full.identity());
+                assert_eq!(edid.detailed.first(), full.detailed.first());
+            }
+        }
+    }
+
+    #[test]
+    fn corpus_bad_checksum_variants() {
+        for data in CORPUS {
+            let full = parse_edid(data).unwrap();
+            for block in 0..data.len() / BLOCK {
+                // Um byte de preenchimento alterado: os campos seguem legíveis, só o checksum acusa.
+                let mut data = data.to_vec();
+                data[block * BLOCK + BLOCK - 1] ^= 0x5A;
+                let edid = parse_edid(&data).unwrap();
+                assert!(!edid.checksum_valid);
+                assert_eq!(edid.identity(), full.identity());
+                assert_eq!(edid.name, full.name);
+                assert_eq!(edid.detailed, full.detailed);
+            }
+        }
+    }
+
+    #[test]
+    fn displayid_name_only_fills_missing_name() {
+        let mut data = base_block();
+        data.extend(displayid_block(0x20, &[&product_block(0x20, "PANEL 2")]));
+        assert_eq!(parse_edid(&data).unwrap().name.as_deref(), Some("DELL U2720Q"));
+
+        data[72..90].copy_from_slice(&text_descriptor(0xFE, "AUO"));
+        seal(&mut data);
+        assert_eq!(parse_edid(&data).unwrap().name.as_deref(), Some("PANEL 2"));
+
+        // Nome vazio ou maior que o bloco não vira nome.
+        let mut data = data[..BLOCK].to_vec();
+        data.extend(displayid_block(0x13, &[&product_block(0x00, "  ")]));
+        assert_eq!(parse_edid(&data).unwrap().name, None);
+        let mut product = product_block(0x00, "X");
+        product[14] = 40;
+        data.truncate(BLOCK);
+        data.extend(displayid_block(0x13, &[&product]));
+        assert_eq!(parse_edid(&data).unwrap().name, None);
+    }
+
+    #[test]
+    fn displayid_timing_units() {
+        let mut data = base_block();
+        data.extend(displayid_block(0x13, &[
+            &timing_block(0x03, 204_023, 7680, 4320, 0x04),
+            &timing_block(0x22, 1_020_115, 7680, 4320, 0x10),
+            // Tipo desconhecido é pulado sem interromper a leitura.
+            &[0x7E, 0, 2, 0xAA, 0xBB],
+            &timing_block(0x22, 131_558, 1840, 1080, 0x00),
+        ]));
+
+        let edid = parse_edid(&data).unwrap();
+        assert!(edid.checksum_valid);
+        let displayid = &edid.detailed[1..];
+        assert_eq!(displayid.iter().map(|d| d.pixel_clock_khz).collect::<Vec<_>>(), [2_040_230, 1_020_115, 131_558]);
+        assert_eq!(displayid.iter().map(|d| d.refresh.round() as u32).collect::<Vec<_>>(), [60, 30, 60]);
+        assert_eq!(displayid.iter().map(|d| d.interlaced).collect::<Vec<_>>(), [false, true, false]);
+    }
+
+    #[test]
+    fn displayid_stops_at_section_end() {
+        let timing = timing_block(0x03, 14_850, 1920, 1080, 0);
+        let mut data = base_block();
+        data.extend(displayid_block(0x13, &[&timing]));
+        assert_eq!(parse_edid(&data).unwrap().detailed.len(), 2);
+
+        // Seção vazia.
+        data.truncate(BLOCK);
+        data.extend(displayid_block(0x13, &[]));
+        assert_eq!(parse_edid(&data).unwrap().detailed.len(), 1);
+
+        // Bloco que declara mais bytes do que a seção tem.
+        let mut overrun = timing.clone();
+        overrun[2] = 40;
+        data.truncate(BLOCK);
+        data.extend(displayid_block(0x13, &[&overrun]));
+        assert_eq!(parse_edid(&data).unwrap().detailed.len(), 1);
+
+        // Tamanho de seção maior que o bloco: lê só até o fim dele.
+        data.truncate(BLOCK);
+        data.extend(displayid_block(0x13, &[&timing]));
+        data[BLOCK + 2] = 0xFF;
+        let edid = parse_edid(&data).unwrap();
+        assert!(!edid.checksum_valid);
+        assert_eq!(edid.detailed.len(), 2);
+    }
+}
@@ -1,8 +1,14 @@
 use windows::Win32::Foundation::{
+    HANDLE,
     LPARAM,
+    POINT,
     RECT,
     GetLastError,
 };
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetCursorPos,
+    GetForegroundWindow,
+};
 use windows::core::{
     Result,
     BOOL,
@@ -22,17 +28,44 @@ use windows::Win32::Graphics::Gdi::{
     ENUM_DISPLAY_SETTINGS_MODE,
     ENUM_DISPLAY_SETTINGS_FLAGS,
     DISP_CHANGE_SUCCESSFUL,
+    DISPLAY_DEVICEW,
+    EnumDisplayDevicesW,
     EnumDisplayMonitors, 
     GetMonitorInfoW, 
     HMONITOR, 
     MONITORINFOEXW, 
     HDC,
+    MONITOR_DEFAULTTONEAREST,
+    MonitorFromPoint,
+    MonitorFromWindow,
 };
 use windows::Win32::Devices::Display::{
+    GetNumberOfPhysicalMonitorsFromHMONITOR,
     GetPhysicalMonitorsFromHMONITOR,
     DestroyPhysicalMonitors,
     SetVCPFeature,
+    GetVCPFeatureAndVCPFeatureReply,
+    GetCapabilitiesStringLength,
+    CapabilitiesRequestAndCapabilitiesReply,
     PHYSICAL_MONITOR,
+    DisplayConfigGetDeviceInfo,
+    DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
+    DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME,
+    DISPLAYCONFIG_SOURCE_DEVICE_NAME,
+    DISPLAYCONFIG_TARGET_DEVICE_NAME,
+    DISPLAYCONFIG_VIDEO_OUTPUT_TECHNOLOGY,
+    DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_EMBEDDED,
+    DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_EXTERNAL,
+    DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_USB_TUNNEL,
+    DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DVI,
+    DISPLAYCONFIG_OUTPUT_TECHNOLOGY_HD15,
+    DISPLAYCONFIG_OUTPUT_TECHNOLOGY_HDMI,
+    DISPLAYCONFIG_OUTPUT_TECHNOLOGY_INTERNAL,
+    DISPLAYCONFIG_OUTPUT_TECHNOLOGY_LVDS,
+    DISPLAYCONFIG_OUTPUT_TECHNOLOGY_MIRACAST,
+    DISPLAYCONFIG_OUTPUT_TECHNOLOGY_UDI_EMBEDDED,
+    MC_MOMENTARY,
+    MC_SET_PARAMETER,
     GetDisplayConfigBufferSizes,
     QueryDisplayConfig,
     SetDisplayConfig,
@@ -45,11 +78,76 @@ use windows::Win32::Devices::Display::{
     SDC_SAVE_TO_DATABASE,
     SDC_USE_SUPPLIED_DISPLAY_CONFIG,
 };
+use windows::Win32::System::Registry::{
+    RegGetValueW,
+    HKEY_LOCAL_MACHINE,
+    RRF_RT_REG_BINARY,
+};
+use super::vcp::{
+    PowerMode,
+    ResetCategory,
+    VcpKind,
+    VcpValue,
+    AUTO_SETUP,
+    BRIGHTNESS,
+    INPUT_SOURCE,
+    POWER_MODE,
+    from_percent,
+};
+use super::capabilities::{
+    Capabilities,
+    parse_capabilities,
+};
+use super::edid::{
+    Edid,
+    manufacturer_name,
+    parse_edid,
+};
+use super::i18n::{t, tr};
+use super::ddc::{
+    DdcChannel,
+    DdcConfig,
+    Transport,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::Instant;
 
-#[derive(Debug)]
+const EDD_GET_DEVICE_INTERFACE_NAME: u32 = 1;
+
+#[derive(Debug, Clone)]
 pub struct DisplaySummary {
     pub index: u32,
     pub name: String,
+    // Identidade estável para as configurações; o nome GDI muda com a ordem das portas.
+    pub key: String,
+    pub friendly_name: String,
+    pub connector: Option<&'static str>,
+}
+
+impl DisplaySummary {
+    // "DELL U2720Q (DP) — Mesa esquerda"
+    pub fn label(&self, nickname: Option<&str>) -> String {
+        let mut label = self.friendly_name.clone();
+        if let Some(connector) = self.connector {
+            label.push_str(&format!(" ({})", tr(connector)));
+        }
+        if let Some(nickname) = nickname.map(str::trim).filter(|n| !n.is_empty()) {
+            label.push_str(&format!(" — {}", nickname));
+        }
+        label
+    }
+
+    // Aceita o índice, o nome do dispositivo (com ou sem \\.\), a chave, o nome amigável ou o apelido.
+    pub fn matches(&self, selector: &str, nickname: Option<&str>) -> bool {
+        let device = |name: &str| name.trim_start_matches("\\\\.\\").to_ascii_uppercase();
+        selector.parse::<u32>().ok() == Some(self.index)
+            || device(&self.name) == device(selector)
+            || self.key.eq_ignore_ascii_case(selector)
+            || self.friendly_name.eq_ignore_ascii_case(selector)
+            || nickname.is_some_and(|n| n.eq_ignore_ascii_case(selector))
+    }
 }
 
 #[derive(Debug)]
@@ -63,12 +161,40 @@ pub struct DisplayInfo {
     pub supported_resolutions: Vec<Resolutions>,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PhysicalTarget {
+    #[default]
+    All,
+    One(u32),
+}
+
+#[derive(Debug, Clone)]
+pub struct PhysicalMonitor {
+    pub index: u32,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 pub struct Resolution {
     pub w: i32,
     pub h: i32,
 }
 
+// RECT não é serializável; esta é a cópia usada nas exportações.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Position {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl From<RECT> for Position {
+    fn from(rect: RECT) -> Self {
+        Self { left: rect.left, top: rect.top, right: rect.right, bottom: rect.bottom }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct Resolutions {
     pub sw: u32,
@@ -106,8 +232,10 @@ pub fn find_properties(index: u32) -> Result<DisplayInfo> {
 
             let mut i = 0u32;
             loop {
-                let mut devmode = DEVMODEW::default();
-                devmode.dmSize = std::mem::size_of::<DEVMODEW>() as u16;
+                let mut devmode = DEVMODEW {
+                    dmSize: std::mem::size_of::<DEVMODEW>() as u16,
+                    ..Default::default()
+                };
 
                 if unsafe {
                     EnumDisplaySettingsExW(
@@ -130,7 +258,7 @@ pub fn find_properties(index: u32) -> Result<DisplayInfo> {
             }
 
             let mut sorted: Vec<Resolutions> = supported_resolutions.into_iter().collect();
-            sorted.sort_by(|a, b| (b.sh).cmp(&(a.sh)));
+            sorted.sort_by_key(|r| std::cmp::Reverse(r.sh));
 
             displays.push(DisplayInfo {
                 index,
@@ -159,7 +287,7 @@ pub fn find_properties(index: u32) -> Result<DisplayInfo> {
         .find(|d| d.index == index)
         .ok_or_else(|| windows::core::Error::new(
             HRESULT::from_win32(0x57),
-            format!("Monitor com index {} não encontrado", index),
+            t!("error-monitor-index-not-found", index = index),
         ))
 }
 
@@ -183,7 +311,13 @@ pub fn list_displays() -> Result<Vec<DisplaySummary>> {
                 .to_string();
             let index = displays.len() as u32;
 
-            displays.push(DisplaySummary { index, name });
+            displays.push(DisplaySummary {
+                index,
+                friendly_name: name.trim_start_matches("\\\\.\\").to_string(),
+                key: name.clone(),
+                name,
+                connector: None,
+            });
         }
         BOOL(1)
     }
@@ -197,9 +331,316 @@ pub fn list_displays() -> Result<Vec<DisplaySummary>> {
         );
     }
 
+    let targets = target_names().unwrap_or_default();
+    let mut keys: HashMap<String, u32> = HashMap::new();
+    for display in &mut displays {
+        let target = targets.get(&display.name);
+        display.connector = target.and_then(|t| connector_name(t.technology));
+        let edid = read_edid(&display.name).ok();
+
+        // Sem EDID, a chave fica sendo o nome GDI, como antes.
+        if let Some(edid) = &edid {
+            let key = edid.identity();
+            // Gêmeos sem número de série ganham um sufixo pela ordem de enumeração.
+            let count = keys.entry(key.clone()).or_default();
+            *count += 1;
+            display.key = if *count > 1 { format!("{}#{}", key, count) } else { key };
+        }
+
+        // Sem nome do driver, o EDID ainda permite algo melhor que "DISPLAY2".
+        if let Some(name) = target.map(|t| t.friendly_name.clone()).filter(|n| !n.is_empty()) {
+            display.friendly_name = name;
+        } else if let Some(edid) = edid {
+            display.friendly_name = match (edid.name.clone(), edid.diagonal_inches()) {
+                (Some(name), _) => name,
+                (None, Some(inches)) => format!("{} {:.0}\"", manufacturer_name(&edid.manufacturer), inches),
+                (None, None) => manufacturer_name(&edid.manufacturer).to_string(),
+            };
+        }
+    }
+
     Ok(displays)
 }
 
+struct TargetName {
+    friendly_name: String,
+    technology: DISPLAYCONFIG_VIDEO_OUTPUT_TECHNOLOGY,
+}
+
+fn target_names() -> Result<HashMap<String, TargetName>> {
+    let mut names = HashMap::new();
+
+    unsafe {
+        let mut num_paths: u32 = 0;
+        let mut num_modes: u32 = 0;
+
+        GetDisplayConfigBufferSizes(QDC_ONLY_ACTIVE_PATHS, &mut num_paths, &mut num_modes).ok()?;
+
+        let mut paths = vec![DISPLAYCONFIG_PATH_INFO::default(); num_paths as usize];
+        let mut modes = vec![DISPLAYCONFIG_MODE_INFO::default(); num_modes as usize];
+
+        QueryDisplayConfig(
+            QDC_ONLY_ACTIVE_PATHS,
+            &mut num_paths,
+            paths.as_mut_ptr(),
+            &mut num_modes,
+            modes.as_mut_ptr(),
+            None,
+        )
+        .ok()?;
+        paths.truncate(num_paths as usize);
+
+        for path in &paths {
+            let mut source = DISPLAYCONFIG_SOURCE_DEVICE_NAME::default();
+            source.header.r#type = DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME;
+            source.header.size = std::mem::size_of::<DISPLAYCONFIG_SOURCE_DEVICE_NAME>() as u32;
+            source.header.adapterId = path.sourceInfo.adapterId;
+            source.header.id = path.sourceInfo.id;
+            if DisplayConfigGetDeviceInfo(&mut source.header) != 0 {
+                continue;
+            }
+
+            let mut target = DISPLAYCONFIG_TARGET_DEVICE_NAME::default();
+            target.header.r#type = DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME;
+            target.header.size = std::mem::size_of::<DISPLAYCONFIG_TARGET_DEVICE_NAME>() as u32;
+            target.header.adapterId = path.targetInfo.adapterId;
+            target.header.id = path.targetInfo.id;
+            if DisplayConfigGetDeviceInfo(&mut target.header) != 0 {
+                continue;
+            }
+
+            let gdi_name = String::from_utf16_lossy(&source.viewGdiDeviceName).trim_end_matches('\0').to_string();
+            let friendly_name = String::from_utf16_lossy(&target.monitorFriendlyDeviceName).trim_end_matches('\0').to_string();
+            // Em clones, o primeiro alvo de cada origem dá o nome.
+            names.entry(gdi_name).or_insert(TargetName { friendly_name, technology: target.outputTechnology });
+        }
+    }
+
+    Ok(names)
+}
+
+// Devolve o nome universal da conexão ou um id do catálogo de mensagens.
+fn connector_name(technology: DISPLAYCONFIG_VIDEO_OUTPUT_TECHNOLOGY) -> Option<&'static str> {
+    match technology {
+        DISPLAYCONFIG_OUTPUT_TECHNOLOGY_HD15 => Some("VGA"),
+        DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DVI => Some("DVI"),
+        DISPLAYCONFIG_OUTPUT_TECHNOLOGY_HDMI => Some("HDMI"),
+        DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_EXTERNAL
+        | DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_USB_TUNNEL => Some("DP"),
+        DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_EMBEDDED
+        | DISPLAYCONFIG_OUTPUT_TECHNOLOGY_LVDS
+        | DISPLAYCONFIG_OUTPUT_TECHNOLOGY_UDI_EMBEDDED
+        | DISPLAYCONFIG_OUTPUT_TECHNOLOGY_INTERNAL => Some("connector-internal"),
+        DISPLAYCONFIG_OUTPUT_TECHNOLOGY_MIRACAST => Some("Miracast"),
+        _ => None,
+    }
+}
+
+// As duas enumerações que precisam concordar, e o que se faz com o resultado delas;
+// separadas para testar monitores que somem no meio de uma operação.
+pub trait DisplayBackend {
+    fn list_displays(&self) -> Result<Vec<DisplaySummary>>;
+    fn find_properties(&self, index: u32) -> Result<DisplayInfo>;
+    fn brightness(&self, index: u32) -> Option<VcpValue>;
+    fn move_display(&self, index: u32, x: i32, y: i32) -> Result<()>;
+}
+
+pub struct SystemDisplays;
+
+impl DisplayBackend for SystemDisplays {
+    fn list_displays(&self) -> Result<Vec<DisplaySummary>> {
+        list_displays()
+    }
+
+    fn find_properties(&self, index: u32) -> Result<DisplayInfo> {
+        find_properties(index)
+    }
+
+    fn brightness(&self, index: u32) -> Option<VcpValue> {
+        get_vcp(index, PhysicalTarget::All, BRIGHTNESS).ok()
+    }
+
+    fn move_display(&self, index: u32, x: i32, y: i32) -> Result<()> {
+        move_display(index, x, y)
+    }
+}
+
+// Enumera `listed`, mas as consultas só enxergam `present`, na ordem atual: um monitor
+// removido depois da enumeração falha ou desloca os índices dos seguintes.
+#[cfg(test)]
+pub struct FakeDisplays {
+    listed: Vec<&'static str>,
+    present: std::cell::RefCell<Vec<&'static str>>,
+    // Depois de tantas consultas por índice, o monitor é desconectado.
+    unplug: std::cell::Cell<Option<(usize, &'static str)>>,
+    moves: std::cell::RefCell<Vec<(u32, i32, i32)>>,
+}
+
+#[cfg(test)]
+impl FakeDisplays {
+    pub fn new(listed: Vec<&'static str>, present: Vec<&'static str>) -> Self {
+        Self { listed, present: present.into(), unplug: Default::default(), moves: Default::default() }
+    }
+
+    pub fn unplug_after(self, queries: usize, name: &'static str) -> Self {
+        self.unplug.set(Some((queries, name)));
+        self
+    }
+
+    pub fn moves(&self) -> Vec<(u32, i32, i32)> {
+        self.moves.borrow().clone()
+    }
+
+    fn present(&self, index: u32) -> Result<&'static str> {
+        if let Some((queries, name)) = self.unplug.get() {
+            match queries {
+                0 => {
+                    self.present.borrow_mut().retain(|n| *n != name);
+                    self.unplug.set(None);
+                }
+                _ => self.unplug.set(Some((queries - 1, name))),
+            }
+        }
+        self.present.borrow().get(index as usize).copied().ok_or_else(|| windows::core::Error::new(
+            HRESULT::from_win32(0x57),
+            t!("error-monitor-index-not-found", index = index),
+        ))
+    }
+}
+
+#[cfg(test)]
+impl DisplayBackend for FakeDisplays {
+    fn list_displays(&self) -> Result<Vec<DisplaySummary>> {
+        Ok(self.listed.iter().enumerate().map(|(index, name)| DisplaySummary {
+            index: index as u32,
+            name: name.to_string(),
+            key: format!("KEY-{}", name),
+            friendly_name: String::new(),
+            connector: None,
+        }).collect())
+    }
+
+    fn find_properties(&self, index: u32) -> Result<DisplayInfo> {
+        let name = self.present(index)?;
+        Ok(DisplayInfo {
+            index,
+            name: name.to_string(),
+            position: RECT { left: index as i32 * 1920, top: 0, right: (index as i32 + 1) * 1920, bottom: 1080 },
+            resolution: Resolution { w: 1920, h: 1080 },
+            is_primary: index == 0,
+            hmonitor: HMONITOR::default(),
+            supported_resolutions: vec![Resolutions { sw: 1920, sh: 1080 }],
+        })
+    }
+
+    fn brightness(&self, index: u32) -> Option<VcpValue> {
+        self.present(index).ok().map(|_| VcpValue { current: 50, max: 100, kind: VcpKind::SetParameter })
+    }
+
+    fn move_display(&self, index: u32, x: i32, y: i32) -> Result<()> {
+        self.present(index)?;
+        self.moves.borrow_mut().push((index, x, y));
+        Ok(())
+    }
+}
+
+// Desconectado (ou com o índice já trocado) entre as duas enumerações: fica de fora.
+pub fn connected_displays(backend: &impl DisplayBackend) -> Result<Vec<(DisplaySummary, DisplayInfo)>> {
+    Ok(backend
+        .list_displays()?
+        .into_iter()
+        .filter_map(|display| {
+            let props = backend.find_properties(display.index).ok().filter(|p| p.name == display.name)?;
+            Some((display, props))
+        })
+        .collect())
+}
+
+pub fn display_under_cursor() -> Result<u32> {
+    let mut point = POINT::default();
+    unsafe { GetCursorPos(&mut point)? };
+    display_index(unsafe { MonitorFromPoint(point, MONITOR_DEFAULTTONEAREST) })
+}
+
+pub fn display_of_foreground_window() -> Result<u32> {
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd.is_invalid() {
+        return display_under_cursor();
+    }
+    display_index(unsafe { MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST) })
+}
+
+// Nome e retângulo (esquerda, topo, direita, base) de cada monitor, na ordem de enumeração.
+pub type Topology = Vec<(String, (i32, i32, i32, i32))>;
+
+// Sem EDID nem modos: barato para consultar a cada poucos segundos.
+pub fn display_topology() -> Topology {
+    let mut topology: Topology = Vec::new();
+
+    unsafe extern "system" fn monitor_enum_proc(
+        hmonitor: HMONITOR,
+        _hdc: HDC,
+        _lprc: *mut RECT,
+        dw_data: LPARAM,
+    ) -> BOOL {
+        let topology = unsafe { &mut *(dw_data.0 as *mut Topology) };
+
+        let mut info = MONITORINFOEXW::default();
+        info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+        if unsafe { GetMonitorInfoW(hmonitor, &mut info.monitorInfo) }.as_bool() {
+            let name = String::from_utf16_lossy(&info.szDevice).trim_end_matches('\0').to_string();
+            let r = info.monitorInfo.rcMonitor;
+            topology.push((name, (r.left, r.top, r.right, r.bottom)));
+        }
+        BOOL(1)
+    }
+
+    unsafe {
+        let _ = EnumDisplayMonitors(
+            None,
+            None,
+            Some(monitor_enum_proc),
+            LPARAM(&mut topology as *mut _ as isize),
+        );
+    }
+
+    topology
+}
+
+// Mesma ordem de EnumDisplayMonitors usada por find_properties e list_displays.
+fn display_index(hmonitor: HMONITOR) -> Result<u32> {
+    let mut monitors: Vec<HMONITOR> = Vec::new();
+
+    unsafe extern "system" fn monitor_enum_proc(
+        hmonitor: HMONITOR,
+        _hdc: HDC,
+        _lprc: *mut RECT,
+        dw_data: LPARAM,
+    ) -> BOOL {
+        let monitors = unsafe { &mut *(dw_data.0 as *mut Vec<HMONITOR>) };
+        monitors.push(hmonitor);
+        BOOL(1)
+    }
+
+    unsafe {
+        let _ = EnumDisplayMonitors(
+            None,
+            None,
+            Some(monitor_enum_proc),
+            LPARAM(&mut monitors as *mut _ as isize),
+        );
+    }
+
+    monitors
+        .iter()
+        .position(|&m| m == hmonitor)
+        .map(|i| i as u32)
+        .ok_or_else(|| windows::core::Error::new(
+            HRESULT::from_win32(0x57),
+            t!("error-monitor-not-found"),
+        ))
+}
+
 pub fn set_resolution(index: u32, w: u32, h: u32) -> Result<()> {
     let display = find_properties(index)?;
 
@@ -215,8 +656,10 @@ pub fn set_resolution(index: u32, w: u32, h: u32) -> Result<()> {
 
         let mut i = 0u32;
         loop {
-            let mut devmode = DEVMODEW::default();
-            devmode.dmSize = std::mem::size_of::<DEVMODEW>() as u16;
+            let mut devmode = DEVMODEW {
+                dmSize: std::mem::size_of::<DEVMODEW>() as u16,
+                ..Default::default()
+            };
 
             if !EnumDisplaySettingsExW(
                 device_name,
@@ -242,7 +685,7 @@ pub fn set_resolution(index: u32, w: u32, h: u32) -> Result<()> {
         let mut mode = best_mode.ok_or_else(|| {
             windows::core::Error::new(
                 HRESULT::from_win32(0x80070057),
-                format!("Resolução {}x{} não suportada no monitor '{}'", w, h, display.name),
+                t!("error-resolution-unsupported", width = w, height = h, monitor = display.name.as_str()),
             )
         })?;
 
@@ -318,7 +761,7 @@ pub fn set_primary_display(index: u32) -> Result<()> {
         if result != 0 {
             return Err(windows::core::Error::new(
                 HRESULT::from_win32(result as u32),
-                format!("SetDisplayConfig falhou com código {}", result),
+                t!("error-set-display-config", code = result),
             ));
         }
     }
@@ -326,30 +769,13 @@ pub fn set_primary_display(index: u32) -> Result<()> {
     Ok(())
 }
 
-pub fn auto_adjust(index: u32) -> Result<()> {
-    let display = find_properties(index)?;
-
-    unsafe {
-        let mut physical_array = vec![PHYSICAL_MONITOR::default(); 1];
-
-        GetPhysicalMonitorsFromHMONITOR(display.hmonitor, &mut physical_array)?;
-
-        let h_physical = physical_array[0].hPhysicalMonitor;
+pub fn auto_adjust(index: u32, target: PhysicalTarget) -> Result<()> {
+    with_physical_monitors(index, target, |channel| channel.set(AUTO_SETUP, 1))?;
 
-        if SetVCPFeature(h_physical, 0x1E, 1) == 0 {
-            let _ = DestroyPhysicalMonitors(&physical_array);
-            let last_error = GetLastError();
-            return Err(windows::core::Error::from_hresult(
-                HRESULT::from_win32(last_error.0)
-            ));
-        }
+    let wait = ddc_config().auto_setup_ms;
+    std::thread::sleep(std::time::Duration::from_millis(wait));
 
-        let _ = DestroyPhysicalMonitors(&physical_array);
-
-        std::thread::sleep(std::time::Duration::from_millis(3000));
-
-        Ok(())
-    }
+    Ok(())
 }
 
 pub fn move_display(index: u32, x: i32, y: i32) -> Result<()> {
@@ -362,18 +788,20 @@ pub fn move_display(index: u32, x: i32, y: i32) -> Result<()> {
     let device_name = PCWSTR(device_wide.as_ptr());
 
     unsafe {
-        let mut devmode = DEVMODEW::default();
-        devmode.dmSize = std::mem::size_of::<DEVMODEW>() as u16;
+        let mut devmode = DEVMODEW {
+            dmSize: std::mem::size_of::<DEVMODEW>() as u16,
+            ..Default::default()
+        };
 
         if !EnumDisplaySettingsExW(
             device_name,
-            ENUM_DISPLAY_SETTINGS_MODE(ENUM_CURRENT_SETTINGS.0 as u32),
+            ENUM_DISPLAY_SETTINGS_MODE(ENUM_CURRENT_SETTINGS.0),
             &mut devmode,
             ENUM_DISPLAY_SETTINGS_FLAGS(0),
         ).as_bool() {
             return Err(windows::core::Error::new(
                 HRESULT::from_win32(0x80070057),
-                format!("Não foi possível obter configuração atual do monitor '{}'", display.name),
+                t!("error-current-settings", monitor = display.name.as_str()),
             ));
         }
 
@@ -399,87 +827,346 @@ pub fn move_display(index: u32, x: i32, y: i32) -> Result<()> {
     }
 }
 
-pub fn display_brightness(index: u32, percent: u32) -> Result<()> {
-    let display = find_properties(index)?;
+pub fn display_brightness(index: u32, target: PhysicalTarget, percent: u32) -> Result<()> {
+    set_brightness_scaled(index, target, percent, &[])
+}
 
-    unsafe {
-        let mut physical_array = vec![PHYSICAL_MONITOR::default(); 1];
+// Máximo do brilho de cada monitor físico, na ordem em que set_brightness_scaled os percorre.
+pub fn brightness_maxima(index: u32, target: PhysicalTarget) -> Result<Vec<u32>> {
+    with_physical_monitors(index, target, |channel| Ok(channel.get(BRIGHTNESS)?.max))
+}
 
-        GetPhysicalMonitorsFromHMONITOR(display.hmonitor, &mut physical_array)?;
+// Cada monitor físico pode ter um máximo diferente; os que faltarem em `maxima` são lidos antes da escrita.
+pub fn set_brightness_scaled(index: u32, target: PhysicalTarget, percent: u32, maxima: &[u32]) -> Result<()> {
+    let mut maxima = maxima.iter();
+    with_physical_monitors(index, target, |channel| {
+        let max = match maxima.next() {
+            Some(max) => *max,
+            None => channel.get(BRIGHTNESS)?.max,
+        };
+        channel.set(BRIGHTNESS, from_percent(percent, max))
+    })?;
 
-        let h_physical = physical_array[0].hPhysicalMonitor;
+    Ok(())
+}
 
-        if SetVCPFeature(h_physical, 0x10, percent) == 0 {
-            let _ = DestroyPhysicalMonitors(&physical_array);
-            let last_error = GetLastError();
-            return Err(windows::core::Error::from_hresult(
-                HRESULT::from_win32(last_error.0)
-            ));
+pub fn reset_monitor(index: u32, target: PhysicalTarget, categories: &[ResetCategory]) -> Result<()> {
+    with_physical_monitors(index, target, |channel| {
+        for category in categories {
+            channel.set(category.code(), 1)?;
         }
+        Ok(())
+    })?;
 
-        let _ = DestroyPhysicalMonitors(&physical_array);
+    Ok(())
+}
 
-        Ok(())
+// Valores atuais por monitor físico, com a posição dele (em modo espelhado as telas não
+// precisam concordar); códigos sem resposta ficam de fora.
+pub fn read_vcp_values(index: u32, target: PhysicalTarget, codes: &[u8]) -> Vec<(u32, Vec<(u8, u32)>)> {
+    let first = match target {
+        PhysicalTarget::All => 0,
+        PhysicalTarget::One(i) => i,
+    };
+    let values = with_physical_monitors(index, target, |channel| {
+        Ok(codes.iter().filter_map(|&code| channel.get(code).ok().map(|value| (code, value.current))).collect())
+    });
+    (first..).zip(values.unwrap_or_default()).collect()
+}
+
+pub fn get_input_source(index: u32, target: PhysicalTarget) -> Result<u8> {
+    // Alguns monitores devolvem lixo no byte alto do valor atual.
+    Ok((get_vcp(index, target, INPUT_SOURCE)?.current & 0xFF) as u8)
+}
+
+pub fn set_input_source(index: u32, target: PhysicalTarget, source: u8) -> Result<()> {
+    set_vcp(index, target, INPUT_SOURCE, source as u32)
+}
+
+pub fn get_power_mode(index: u32, target: PhysicalTarget) -> Result<PowerMode> {
+    let value = get_vcp(index, target, POWER_MODE)?.current;
+
+    PowerMode::from_value(value).ok_or_else(|| windows::core::Error::new(
+        HRESULT::from_win32(0x0D),
+        t!("error-unknown-power-mode", value = format!("0x{:02X}", value)),
+    ))
+}
+
+pub fn set_power_mode(index: u32, target: PhysicalTarget, mode: PowerMode) -> Result<()> {
+    set_vcp(index, target, POWER_MODE, mode as u32)
+}
+
+pub fn turn_off_others(index: u32, mode: PowerMode) -> Result<()> {
+    let mut result = Ok(());
+
+    for display in list_displays()? {
+        if display.index == index {
+            continue;
+        }
+        // Continua nos demais monitores mesmo se um deles não responder.
+        if let Err(e) = set_power_mode(display.index, PhysicalTarget::All, mode) {
+            result = Err(e);
+        }
     }
+
+    result
 }
 
-pub fn reset_monitor(index: u32) -> Result<()> {
+pub fn get_capabilities(index: u32, target: PhysicalTarget) -> Result<Capabilities> {
     let display = find_properties(index)?;
 
+    let raw = first_physical_monitor(index, target, |channel| channel.capabilities())?;
+
+    let capabilities = parse_capabilities(&raw).map_err(|e| windows::core::Error::new(
+        HRESULT::from_win32(0x0D),
+        t!("error-invalid-capabilities", monitor = display.name.as_str(), reason = e.to_string()),
+    ))?;
+
+    // O modelo informado nas capacidades é a chave da tabela de quirks.
+    if let Some(model) = &capabilities.model
+        && let Ok(mut state) = DDC_STATE.lock()
+    {
+        state.models.insert(display.name.clone(), model.clone());
+    }
+
+    Ok(capabilities)
+}
+
+pub fn get_edid(index: u32) -> Result<Edid> {
+    let display = find_properties(index)?;
+    read_edid(&display.name)
+}
+
+fn read_edid(device_name: &str) -> Result<Edid> {
+    let device_wide: Vec<u16> = device_name.encode_utf16().chain(std::iter::once(0)).collect();
+
+    let mut device = DISPLAY_DEVICEW {
+        cb: std::mem::size_of::<DISPLAY_DEVICEW>() as u32,
+        ..Default::default()
+    };
+    if !unsafe { EnumDisplayDevicesW(PCWSTR(device_wide.as_ptr()), 0, &mut device, EDD_GET_DEVICE_INTERFACE_NAME) }.as_bool() {
+        return Err(last_error());
+    }
+
+    // \\?\DISPLAY#DELA0A5#5&1a2b3c&0&UID4352#{...} -> DISPLAY\DELA0A5\5&1a2b3c&0&UID4352
+    let interface = String::from_utf16_lossy(&device.DeviceID).trim_end_matches('\0').to_string();
+    let instance: Vec<&str> = interface.trim_start_matches("\\\\?\\").split('#').take(3).collect();
+    if instance.len() < 3 {
+        return Err(windows::core::Error::new(
+            HRESULT::from_win32(0x02),
+            t!("error-device-unidentified", monitor = device_name),
+        ));
+    }
+    let key = format!("SYSTEM\\CurrentControlSet\\Enum\\{}\\Device Parameters", instance.join("\\"));
+    let key_wide: Vec<u16> = key.encode_utf16().chain(std::iter::once(0)).collect();
+    let value_wide: Vec<u16> = "EDID".encode_utf16().chain(std::iter::once(0)).collect();
+
+    // Sem buffer, a primeira chamada só informa o tamanho: EDIDs com várias extensões passam de 1 KiB.
+    let read = |data: Option<&mut [u8]>, size: &mut u32| unsafe {
+        RegGetValueW(
+            HKEY_LOCAL_MACHINE,
+            PCWSTR(key_wide.as_ptr()),
+            PCWSTR(value_wide.as_ptr()),
+            RRF_RT_REG_BINARY,
+            None,
+            data.map(|d| d.as_mut_ptr() as *mut _),
+            Some(size),
+        )
+    };
+    let mut size = 0u32;
+    read(None, &mut size).ok()?;
+    let mut data = vec![0u8; size as usize];
+    read(Some(&mut data), &mut size).ok()?;
+    data.truncate(size as usize);
+
+    parse_edid(&data).map_err(|e| windows::core::Error::new(
+        HRESULT::from_win32(0x0D),
+        t!("error-invalid-edid", monitor = device_name, reason = e.to_string()),
+    ))
+}
+
+pub fn get_vcp(index: u32, target: PhysicalTarget, code: u8) -> Result<VcpValue> {
+    first_physical_monitor(index, target, |channel| channel.get(code))
+}
+
+pub fn set_vcp(index: u32, target: PhysicalTarget, code: u8, value: u32) -> Result<()> {
+    with_physical_monitors(index, target, |channel| channel.set(code, value))?;
+
+    Ok(())
+}
+
+pub fn list_physical_monitors(index: u32) -> Result<Vec<PhysicalMonitor>> {
+    let mut position = 0;
+
+    with_physical_monitors(index, PhysicalTarget::All, |channel| {
+        position += 1;
+        Ok(PhysicalMonitor {
+            index: position - 1,
+            description: channel.transport().description.clone(),
+        })
+    })
+}
+
+fn with_physical_monitors<T>(
+    index: u32,
+    target: PhysicalTarget,
+    mut f: impl FnMut(&mut DdcChannel<PhysicalTransport>) -> Result<T>,
+) -> Result<Vec<T>> {
+    let display = find_properties(index)?;
+    let config = ddc_config();
+
     unsafe {
-        let mut physical_array = vec![PHYSICAL_MONITOR::default(); 1];
+        let mut count: u32 = 0;
+
+        GetNumberOfPhysicalMonitorsFromHMONITOR(display.hmonitor, &mut count)?;
+
+        let mut physical_array = vec![PHYSICAL_MONITOR::default(); count.max(1) as usize];
 
         GetPhysicalMonitorsFromHMONITOR(display.hmonitor, &mut physical_array)?;
 
-        let h_physical = physical_array[0].hPhysicalMonitor;
+        let selected: Vec<(u32, &PHYSICAL_MONITOR)> = match target {
+            PhysicalTarget::All => (0..).zip(physical_array.iter()).collect(),
+            PhysicalTarget::One(i) => physical_array.get(i as usize).map(|m| (i, m)).into_iter().collect(),
+        };
+
+        if selected.is_empty() {
+            let _ = DestroyPhysicalMonitors(&physical_array);
+            return Err(windows::core::Error::new(
+                HRESULT::from_win32(0x57),
+                t!("error-physical-not-found", target = format!("{:?}", target), monitor = display.name.as_str()),
+            ));
+        }
 
-        let reset_codes = [0x08, 0x04, 0x06, 0x05];
+        // Um único lock serializa o barramento DDC entre as threads do app.
+        let mut state = DDC_STATE.lock().unwrap_or_else(|e| e.into_inner());
+        let model = state.models.get(&display.name).cloned().unwrap_or_default();
+
+        // Em modo espelhado aplica em todos e devolve o primeiro erro.
+        let mut results = Vec::new();
+        let mut error = None;
+        for (position, monitor) in selected {
+            let key = (display.name.clone(), position);
+            // PHYSICAL_MONITOR é empacotada; copia o campo antes de referenciá-lo.
+            let description = monitor.szPhysicalMonitorDescription;
+            let transport = PhysicalTransport {
+                handle: monitor.hPhysicalMonitor,
+                description: String::from_utf16_lossy(&description).trim_end_matches('\0').to_string(),
+            };
+            let mut channel = DdcChannel::new(transport, config.clone(), &model, state.last_command.get(&key).copied());
+
+            match f(&mut channel) {
+                Ok(value) => results.push(value),
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            }
 
-        for code in reset_codes {
-            if SetVCPFeature(h_physical, code, 1) == 0 {
-                let _ = DestroyPhysicalMonitors(&physical_array);
-                let last_error = GetLastError();
-                return Err(windows::core::Error::from_hresult(
-                    HRESULT::from_win32(last_error.0)
-                ));
+            if let Some(last) = channel.last_command() {
+                state.last_command.insert(key, last);
             }
         }
+        drop(state);
 
         let _ = DestroyPhysicalMonitors(&physical_array);
 
-        Ok(())
+        match error {
+            Some(e) => Err(e),
+            None => Ok(results),
+        }
     }
 }
 
-pub fn verify_vcp(index: u32) -> Result<(bool, u32)> {
-    use windows::Win32::Devices::Display::{
-        GetVCPFeatureAndVCPFeatureReply,
-        MC_MOMENTARY,
+fn first_physical_monitor<T>(
+    index: u32,
+    target: PhysicalTarget,
+    f: impl FnMut(&mut DdcChannel<PhysicalTransport>) -> Result<T>,
+) -> Result<T> {
+    let target = match target {
+        PhysicalTarget::All => PhysicalTarget::One(0),
+        target => target,
     };
 
-    let display = find_properties(index)?;
-
-    unsafe {
-        let mut physical_array = vec![PHYSICAL_MONITOR::default(); 1];
-
-        GetPhysicalMonitorsFromHMONITOR(display.hmonitor, &mut physical_array)?;
+    with_physical_monitors(index, target, f)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| windows::core::Error::from_hresult(HRESULT::from_win32(0x57)))
+}
 
-        let h_physical = physical_array[0].hPhysicalMonitor;
+struct PhysicalTransport {
+    handle: HANDLE,
+    description: String,
+}
 
+impl Transport for PhysicalTransport {
+    fn get(&mut self, code: u8) -> Result<VcpValue> {
         let mut current_value: u32 = 0;
         let mut max_value: u32 = 0;
         let mut vcp_type = MC_MOMENTARY;
 
-        let supported = GetVCPFeatureAndVCPFeatureReply(
-            h_physical,
-            0x10,
-            Some(&mut vcp_type),
-            &mut current_value,
-            Some(&mut max_value),
-        ) != 0;
+        if unsafe {
+            GetVCPFeatureAndVCPFeatureReply(
+                self.handle,
+                code,
+                Some(&mut vcp_type),
+                &mut current_value,
+                Some(&mut max_value),
+            )
+        } == 0 {
+            return Err(last_error());
+        }
 
-        let _ = DestroyPhysicalMonitors(&physical_array);
+        let kind = if vcp_type == MC_SET_PARAMETER {
+            VcpKind::SetParameter
+        } else {
+            VcpKind::Momentary
+        };
 
-        Ok((supported, current_value))
+        Ok(VcpValue { current: current_value, max: max_value, kind })
     }
+
+    fn set(&mut self, code: u8, value: u32) -> Result<()> {
+        if unsafe { SetVCPFeature(self.handle, code, value) } == 0 {
+            return Err(last_error());
+        }
+
+        Ok(())
+    }
+
+    fn capabilities(&mut self) -> Result<String> {
+        unsafe {
+            let mut length: u32 = 0;
+            if GetCapabilitiesStringLength(self.handle, &mut length) == 0 {
+                return Err(last_error());
+            }
+
+            let mut buffer = vec![0u8; length as usize];
+            if CapabilitiesRequestAndCapabilitiesReply(self.handle, &mut buffer) == 0 {
+                return Err(last_error());
+            }
+
+            Ok(String::from_utf8_lossy(&buffer).into_owned())
+        }
+    }
+}
+
+#[derive(Default)]
+struct DdcState {
+    last_command: HashMap<(String, u32), Instant>,
+    models: HashMap<String, String>,
+}
+
+static DDC_CONFIG: LazyLock<Mutex<DdcConfig>> = LazyLock::new(Mutex::default);
+static DDC_STATE: LazyLock<Mutex<DdcState>> = LazyLock::new(Mutex::default);
+
+pub fn configure_ddc(config: DdcConfig) {
+    *DDC_CONFIG.lock().unwrap_or_else(|e| e.into_inner()) = config;
+}
+
+fn ddc_config() -> DdcConfig {
+    DDC_CONFIG.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn last_error() -> windows::core::Error {
+    let last_error = unsafe { GetLastError() };
+    windows::core::Error::from_hresult(HRESULT::from_win32(last_error.0))
 }
@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CurvePoint {
+    pub level: u32,
+    pub value: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupMember {
+    pub monitor: String,
+    pub curve: Vec<CurvePoint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BrightnessGroup {
+    pub name: String,
+    pub members: Vec<GroupMember>,
+}
+
+impl BrightnessGroup {
+    pub fn member(&self, monitor: &str) -> Option<&GroupMember> {
+        self.members.iter().find(|m| m.monitor == monitor)
+    }
+
+    pub fn member_mut(&mut self, monitor: &str) -> Option<&mut GroupMember> {
+        self.members.iter_mut().find(|m| m.monitor == monitor)
+    }
+}
+
+pub fn group_of<'a>(groups: &'a [BrightnessGroup], monitor: &str) -> Option<&'a BrightnessGroup> {
+    groups.iter().find(|g| g.member(monitor).is_some())
+}
+
+pub fn map_level(curve: &[CurvePoint], level: u32) -> Option<u32> {
+    let mut points = curve.to_vec();
+    points.sort_by_key(|p| p.level);
+
+    let first = points.first()?;
+    let last = points.last()?;
+    if level <= first.level {
+        return Some(first.value);
+    }
+    if level >= last.level {
+        return Some(last.value);
+    }
+
+    let upper = points.iter().position(|p| p.level >= level)?;
+    let (a, b) = (points[upper - 1], points[upper]);
+    if b.level == a.level {
+        return Some(b.value);
+    }
+
+    let t = (level - a.level) as f64 / (b.level - a.level) as f64;
+    let value = a.value as f64 + (b.value as f64 - a.value as f64) * t;
+    Some(value.round() as u32)
+}
+
+pub fn invert_level(curve: &[CurvePoint], value: u32) -> Option<u32> {
+    let mut points = curve.to_vec();
+    points.sort_by_key(|p| p.level);
+
+    // Procura o primeiro segmento que contém o valor; curvas não monotônicas usam o nível mais baixo.
+    for pair in points.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let (low, high) = (a.value.min(b.value), a.value.max(b.value));
+        if (low..=high).contains(&value) {
+            if a.value == b.value {
+                return Some(a.level);
+            }
+            let t = (value as f64 - a.value as f64) / (b.value as f64 - a.value as f64);
+            return Some((a.level as f64 + (b.level as f64 - a.level as f64) * t).round() as u32);
+        }
+    }
+
+    let first = points.first()?;
+    let last = points.last()?;
+    Some(if value.abs_diff(first.value) <= value.abs_diff(last.value) { first.level } else { last.level })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(points: &[(u32, u32)]) -> Vec<CurvePoint> {
+        points.iter().map(|&(level, value)| CurvePoint { level, value }).collect()
+    }
+
+    #[test]
+    fn empty_curve() {
+        assert_eq!(map_level(&[], 50), None);
+        assert_eq!(invert_level(&[], 50), None);
+    }
+
+    #[test]
+    fn map_interpolates_and_rounds() {
+        let c = curve(&[(0, 0), (100, 255)]);
+        assert_eq!(map_level(&c, 50), Some(128));
+        assert_eq!(map_level(&c, 1), Some(3));
+        // A ordem dos pontos na configuração não importa.
+        let c = curve(&[(100, 80), (0, 10), (50, 20)]);
+        assert_eq!(map_level(&c, 25), Some(15));
+        assert_eq!(map_level(&c, 75), Some(50));
+    }
+
+    #[test]
+    fn map_clamps_outside_curve() {
+        let c = curve(&[(20, 30), (80, 70)]);
+        assert_eq!(map_level(&c, 0), Some(30));
+        assert_eq!(map_level(&c, 20), Some(30));
+        assert_eq!(map_level(&c, 80), Some(70));
+        assert_eq!(map_level(&c, 100), Some(70));
+        assert_eq!(map_level(&c, u32::MAX), Some(70));
+    }
+
+    #[test]
+    fn map_inverted_range() {
+        let c = curve(&[(0, 100), (100, 0)]);
+        assert_eq!(map_level(&c, 0), Some(100));
+        assert_eq!(map_level(&c, 30), Some(70));
+        assert_eq!(map_level(&c, 100), Some(0));
+    }
+
+    #[test]
+    fn map_repeated_level() {
+        // Dois pontos no mesmo nível: vale o primeiro da lista, já que a ordenação é estável.
+        let c = curve(&[(0, 0), (50, 20), (50, 60), (100, 100)]);
+        assert_eq!(map_level(&c, 50), Some(20));
+        assert_eq!(map_level(&c, 75), Some(80));
+        let c = curve(&[(40, 10), (40, 90)]);
+        assert_eq!(map_level(&c, 0), Some(10));
+        assert_eq!(map_level(&c, 100), Some(90));
+    }
+
+    #[test]
+    fn invert_interpolates() {
+        let c = curve(&[(0, 10), (100, 60)]);
+        assert_eq!(invert_level(&c, 35), Some(50));
+        assert_eq!(invert_level(&c, 10), Some(0));
+        assert_eq!(invert_level(&c, 60), Some(100));
+    }
+
+    #[test]
+    fn invert_clamps_to_nearest_end() {
+        let c = curve(&[(0, 10), (100, 60)]);
+        assert_eq!(invert_level(&c, 0), Some(0));
+        assert_eq!(invert_level(&c, 255), Some(100));
+    }
+
+    #[test]
+    fn invert_inverted_range() {
+        let c = curve(&[(0, 100), (100, 0)]);
+        assert_eq!(invert_level(&c, 70), Some(30));
+        assert_eq!(invert_level(&c, 100), Some(0));
+        assert_eq!(invert_level(&c, 0), Some(100));
+    }
+
+    #[test]
+    fn invert_flat_segment() {
+        // Valor mínimo igual ao máximo: fica com o nível mais baixo do trecho plano.
+        let c = curve(&[(0, 40), (100, 40)]);
+        assert_eq!(invert_level(&c, 40), Some(0));
+        assert_eq!(invert_level(&c, 10), Some(0));
+        let c = curve(&[(30, 50)]);
+        assert_eq!(invert_level(&c, 50), Some(30));
+        assert_eq!(map_level(&c, 0), Some(50));
+    }
+
+    #[test]
+    fn round_trip_at_ends() {
+        for points in [
+            vec![(0, 0), (100, 100)],
+            vec![(0, 0), (100, 255)],
+            vec![(0, 100), (100, 0)],
+            vec![(0, 5), (30, 20), (70, 60), (100, 90)],
+        ] {
+            let c = curve(&points);
+            for level in [0, 100] {
+                let value = map_level(&c, level).unwrap();
+                assert_eq!(invert_level(&c, value), Some(level), "curva {points:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+use windows::Win32::Foundation::{LPARAM, WPARAM};
+use windows::Win32::System::Threading::GetCurrentThreadId;
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    HOT_KEY_MODIFIERS,
+    MOD_ALT,
+    MOD_CONTROL,
+    MOD_NOREPEAT,
+    MOD_SHIFT,
+    MOD_WIN,
+    RegisterHotKey,
+    UnregisterHotKey,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetMessageW,
+    MSG,
+    PM_NOREMOVE,
+    PeekMessageW,
+    PostThreadMessageW,
+    WM_APP,
+    WM_HOTKEY,
+};
+use windows::core::Result;
+
+use super::functions::{
+    PhysicalTarget,
+    display_brightness,
+    display_of_foreground_window,
+    display_under_cursor,
+    get_vcp,
+    list_displays,
+    set_input_source,
+};
+use super::profiles::{Profile, apply_profile};
+use super::settings::Settings;
+use super::vcp::BRIGHTNESS;
+
+// Pede à thread dos atalhos que registre tudo de novo.
+const WM_RELOAD: u32 = WM_APP + 1;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyTarget {
+    #[default]
+    Focused,
+    UnderCursor,
+    All,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HotkeyAction {
+    BrightnessUp,
+    BrightnessDown,
+    InputSource { source: u8 },
+    Profile { name: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hotkey {
+    pub keys: String,
+    pub action: HotkeyAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HotkeySettings {
+    pub enabled: bool,
+    pub step: u32,
+    pub target: HotkeyTarget,
+    pub bindings: Vec<Hotkey>,
+}
+
+impl Default for HotkeySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            step: 10,
+            target: HotkeyTarget::Focused,
+            bindings: vec![
+                Hotkey { keys: "Ctrl+Alt+PageUp".to_string(), action: HotkeyAction::BrightnessUp },
+                Hotkey { keys: "Ctrl+Alt+PageDown".to_string(), action: HotkeyAction::BrightnessDown },
+            ],
+        }
+    }
+}
+
+// "Ctrl+Alt+PageUp" -> (modificadores, tecla virtual).
+pub fn parse_keys(keys: &str) -> Option<(HOT_KEY_MODIFIERS, u32)> {
+    let mut modifiers = HOT_KEY_MODIFIERS(0);
+    let mut key = None;
+
+    for part in keys.split('+').map(str::trim) {
+        match part.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => modifiers |= MOD_CONTROL,
+            "alt" => modifiers |= MOD_ALT,
+            "shift" => modifiers |= MOD_SHIFT,
+            "win" => modifiers |= MOD_WIN,
+            _ if key.is_some() => return None,
+            "plus" => key = Some(0xBB),
+            name => key = Some(virtual_key(name)?),
+        }
+    }
+
+    // Sem modificador o atalho roubaria a tecla de todos os programas.
+    if modifiers.0 == 0 {
+        return None;
+    }
+    key.map(|key| (modifiers, key))
+}
+
+fn virtual_key(name: &str) -> Option<u32> {
+    let key = match name {
+        "up" => 0x26,
+        "down" => 0x28,
+        "left" => 0x25,
+        "right" => 0x27,
+        "pageup" => 0x21,
+        "pagedown" => 0x22,
+        "home" => 0x24,
+        "end" => 0x23,
+        "insert" => 0x2D,
+        "delete" => 0x2E,
+        "space" => 0x20,
+        "minus" | "-" => 0xBD,
+        "," => 0xBC,
+        "." => 0xBE,
+        _ => {
+            let bytes = name.as_bytes();
+            match bytes {
+                [c] if c.is_ascii_alphanumeric() => c.to_ascii_uppercase() as u32,
+                [b'f', ..] => {
+                    let n: u32 = name[1..].parse().ok()?;
+                    if !(1..=24).contains(&n) {
+                        return None;
+                    }
+                    0x70 + n - 1
+                }
+                _ => return None,
+            }
+        }
+    };
+    Some(key)
+}
+
+#[derive(Default)]
+struct Shared {
+    settings: HotkeySettings,
+    profiles: Vec<Profile>,
+    failed: Vec<String>,
+}
+
+pub struct Hotkeys {
+    thread_id: u32,
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl Hotkeys {
+    pub fn new(settings: &Settings) -> Self {
+        let shared = Arc::new(Mutex::new(Shared {
+            settings: settings.hotkeys.clone(),
+            profiles: settings.profiles.clone(),
+            failed: Vec::new(),
+        }));
+        let (sender, receiver) = mpsc::channel();
+        let thread_shared = shared.clone();
+        thread::spawn(move || run(thread_shared, sender));
+
+        Self { thread_id: receiver.recv().unwrap_or_default(), shared }
+    }
+
+    pub fn update(&self, settings: &Settings) {
+        if let Ok(mut shared) = self.shared.lock() {
+            shared.settings = settings.hotkeys.clone();
+            shared.profiles = settings.profiles.clone();
+        }
+        unsafe {
+            let _ = PostThreadMessageW(self.thread_id, WM_RELOAD, WPARAM(0), LPARAM(0));
+        }
+    }
+
+    // Atalhos inválidos ou já registrados por outro programa.
+    pub fn failed(&self) -> Vec<String> {
+        self.shared.lock().map(|shared| shared.failed.clone()).unwrap_or_default()
+    }
+}
+
+fn run(shared: Arc<Mutex<Shared>>, ready: Sender<u32>) {
+    // Os atalhos pertencem à thread que os registra e chegam na fila dela.
+    let mut msg = MSG::default();
+    // Cria a fila antes de divulgar o id; sem ela PostThreadMessageW falha.
+    unsafe {
+        let _ = PeekMessageW(&mut msg, None, 0, 0, PM_NOREMOVE);
+    }
+    let _ = ready.send(unsafe { GetCurrentThreadId() });
+    let mut registered = register(&shared);
+
+    while unsafe { GetMessageW(&mut msg, None, 0, 0) }.as_bool() {
+        match msg.message {
+            WM_HOTKEY => {
+                // A ação vem do que foi registrado: update() pode ter trocado a lista antes do WM_RELOAD.
+                let Some(action) = registered.get(&(msg.wParam.0 as i32)) else {
+                    continue;
+                };
+                let snapshot = match shared.lock() {
+                    Ok(shared) => (shared.settings.clone(), shared.profiles.clone()),
+                    Err(_) => return,
+                };
+                let _ = trigger(action, &snapshot.0, &snapshot.1);
+            }
+            WM_RELOAD => {
+                for id in registered.drain().map(|(id, _)| id) {
+                    unsafe {
+                        let _ = UnregisterHotKey(None, id);
+                    }
+                }
+                registered = register(&shared);
+            }
+            _ => {}
+        }
+    }
+}
+
+// Id do atalho registrado -> ação que ele dispara.
+fn register(shared: &Mutex<Shared>) -> HashMap<i32, HotkeyAction> {
+    let Ok(mut shared) = shared.lock() else {
+        return HashMap::new();
+    };
+    let mut registered = HashMap::new();
+    shared.failed.clear();
+
+    if shared.settings.enabled {
+        for (id, binding) in shared.settings.bindings.clone().into_iter().enumerate() {
+            let ok = parse_keys(&binding.keys).is_some_and(|(modifiers, key)| unsafe {
+                RegisterHotKey(None, id as i32, modifiers | MOD_NOREPEAT, key).is_ok()
+            });
+            if ok {
+                registered.insert(id as i32, binding.action);
+            } else {
+                shared.failed.push(binding.keys.clone());
+            }
+        }
+    }
+
+    registered
+}
+
+fn trigger(action: &HotkeyAction, settings: &HotkeySettings, profiles: &[Profile]) -> Result<()> {
+    if let HotkeyAction::Profile { name } = action {
+        return match profiles.iter().find(|p| &p.name == name) {
+            Some(profile) => apply_profile(profile),
+            None => Ok(()),
+        };
+    }
+
+    let targets = match settings.target {
+        HotkeyTarget::Focused => vec![display_of_foreground_window()?],
+        HotkeyTarget::UnderCursor => vec![display_under_cursor()?],
+        HotkeyTarget::All => list_displays()?.into_iter().map(|d| d.index).collect(),
+    };
+
+    let mut result = Ok(());
+    for index in targets {
+        let outcome = match action {
+            HotkeyAction::BrightnessUp => adjust(index, settings.step as i32),
+            HotkeyAction::BrightnessDown => adjust(index, -(settings.step as i32)),
+            HotkeyAction::InputSource { source } => set_input_source(index, PhysicalTarget::All, *source),
+            HotkeyAction::Profile { .. } => Ok(()),
+        };
+        if let Err(e) = outcome {
+            result = Err(e);
+        }
+    }
+
+    result
+}
+
+fn adjust(index: u32, delta: i32) -> Result<()> {
+    let current = get_vcp(index, PhysicalTarget::All, BRIGHTNESS)?.percent() as i32;
+    display_brightness(index, PhysicalTarget::All, (current + delta).clamp(0, 100) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CTRL_ALT: HOT_KEY_MODIFIERS = HOT_KEY_MODIFIERS(MOD_CONTROL.0 | MOD_ALT.0);
+
+    #[test]
+    fn parses_modifiers_and_key() {
+        assert_eq!(parse_keys("Ctrl+Alt+PageUp"), Some((CTRL_ALT, 0x21)));
+        assert_eq!(parse_keys("Win+Shift+B"), Some((HOT_KEY_MODIFIERS(MOD_WIN.0 | MOD_SHIFT.0), 0x42)));
+        assert_eq!(parse_keys(" Control + Alt + 7 "), Some((CTRL_ALT, 0x37)));
+        // Modificadores repetidos não mudam nada.
+        assert_eq!(parse_keys("Ctrl+Ctrl+Alt+Up"), Some((CTRL_ALT, 0x26)));
+    }
+
+    #[test]
+    fn ignores_case() {
+        for keys in ["ctrl+alt+pagedown", "CTRL+ALT+PAGEDOWN", "cTrL+AlT+PaGeDoWn"] {
+            assert_eq!(parse_keys(keys), Some((CTRL_ALT, 0x22)), "{keys}");
+        }
+        assert_eq!(parse_keys("Ctrl+Alt+b"), parse_keys("Ctrl+Alt+B"));
+        assert_eq!(parse_keys("ctrl+alt+f12"), Some((CTRL_ALT, 0x7B)));
+    }
+
+    #[test]
+    fn rejects_modifier_only_input() {
+        assert_eq!(parse_keys("Ctrl+Alt"), None);
+        assert_eq!(parse_keys("Shift"), None);
+        assert_eq!(parse_keys(""), None);
+        // Tecla sem modificador roubaria a tecla de todos os programas.
+        assert_eq!(parse_keys("PageUp"), None);
+        assert_eq!(parse_keys("F5"), None);
+    }
+
+    #[test]
+    fn rejects_two_keys() {
+        assert_eq!(parse_keys("Ctrl+A+B"), None);
+        assert_eq!(parse_keys("Ctrl+Up+Plus"), None);
+        assert_eq!(parse_keys("Ctrl+Plus+Plus"), None);
+    }
+
+    #[test]
+    fn function_key_bounds() {
+        assert_eq!(virtual_key("f1"), Some(0x70));
+        assert_eq!(virtual_key("f24"), Some(0x87));
+        assert_eq!(virtual_key("f0"), None);
+        assert_eq!(virtual_key("f25"), None);
+        assert_eq!(virtual_key("f-1"), None);
+        assert_eq!(virtual_key("fx"), None);
+        // "f" sozinho é a letra F.
+        assert_eq!(virtual_key("f"), Some(0x46));
+        assert_eq!(parse_keys("Alt+F24"), Some((MOD_ALT, 0x87)));
+        assert_eq!(parse_keys("Alt+F25"), None);
+    }
+
+    #[test]
+    fn plus_and_punctuation() {
+        // O "+" separa as teclas, então a tecla de mais se escreve "Plus".
+        assert_eq!(parse_keys("Ctrl+Plus"), Some((MOD_CONTROL, 0xBB)));
+        assert_eq!(parse_keys("Ctrl+PLUS"), Some((MOD_CONTROL, 0xBB)));
+        assert_eq!(parse_keys("Ctrl++"), None);
+        assert_eq!(parse_keys("Ctrl+Minus"), Some((MOD_CONTROL, 0xBD)));
+        assert_eq!(parse_keys("Ctrl+-"), Some((MOD_CONTROL, 0xBD)));
+        assert_eq!(parse_keys("Ctrl+,"), Some((MOD_CONTROL, 0xBC)));
+        assert_eq!(virtual_key("é"), None);
+        assert_eq!(virtual_key("tab"), None);
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use serde::{Deserialize, Serialize};
+use unic_langid::LanguageIdentifier;
+use windows::Win32::Globalization::GetUserDefaultLocaleName;
+
+const CATALOGUES: [(&str, &str); 2] = [
+    ("pt-BR", include_str!("../../locales/pt-BR.ftl")),
+    ("en-US", include_str!("../../locales/en-US.ftl")),
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    #[serde(rename = "pt-BR")]
+    Portuguese,
+    #[serde(rename = "en-US")]
+    English,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::Auto, Language::Portuguese, Language::English];
+
+    // Nome exibido no seletor, sempre no próprio idioma.
+    pub fn label(self) -> String {
+        match self {
+            Language::Auto => tr("language-auto"),
+            Language::Portuguese => "Português".to_string(),
+            Language::English => "English".to_string(),
+        }
+    }
+
+    fn catalogue(self) -> u8 {
+        match self {
+            Language::Auto => system_language().catalogue(),
+            Language::Portuguese => 0,
+            Language::English => 1,
+        }
+    }
+}
+
+static BUNDLES: LazyLock<Vec<FluentBundle<FluentResource>>> = LazyLock::new(|| {
+    CATALOGUES
+        .iter()
+        .map(|(locale, source)| {
+            let locale: LanguageIdentifier = locale.parse().unwrap_or_default();
+            let mut bundle = FluentBundle::new_concurrent(vec![locale]);
+            // As marcas de isolamento Unicode aparecem como caixas no egui e no console.
+            bundle.set_use_isolating(false);
+            let resource = FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, _)| resource);
+            let _ = bundle.add_resource(resource);
+            bundle
+        })
+        .collect()
+});
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_language(language: Language) {
+    CURRENT.store(language.catalogue(), Ordering::Relaxed);
+}
+
+fn system_language() -> Language {
+    let mut buffer = [0u16; 85];
+    let len = unsafe { GetUserDefaultLocaleName(&mut buffer) };
+    let locale = String::from_utf16_lossy(&buffer[..(len.max(1) - 1) as usize]);
+    if locale.to_ascii_lowercase().starts_with("pt") {
+        Language::Portuguese
+    } else {
+        Language::English
+    }
+}
+
+pub fn tr(id: &str) -> String {
+    tr_args(id, None)
+}
+
+// Mensagens ausentes caem no português e, por fim, no próprio id.
+pub fn tr_args(id: &str, args: Option<&FluentArgs>) -> String {
+    let current = CURRENT.load(Ordering::Relaxed) as usize;
+    [current, 0]
+        .into_iter()
+        .filter_map(|i| BUNDLES.get(i))
+        .find_map(|bundle| {
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = Vec::new();
+            Some(bundle.format_pattern(pattern, args, &mut errors).into_owned())
+        })
+        .unwrap_or_else(|| id.to_string())
+}
+
+macro_rules! t {
+    ($id:expr) => {
+        $crate::libs::i18n::tr($id)
+    };
+    ($id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::libs::i18n::tr_args($id, Some(&args))
+    }};
+}
+pub(crate) use t;
@@ -0,0 +1,282 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use windows::core::{HRESULT, Result};
+
+use super::functions::{
+    DisplayBackend,
+    DisplayInfo,
+    DisplaySummary,
+    PhysicalTarget,
+    Position,
+    Resolution,
+    SystemDisplays,
+    connected_displays,
+    find_properties,
+    get_capabilities,
+    get_edid,
+    get_vcp,
+    list_displays,
+};
+use super::i18n::tr;
+use super::vcp::BRIGHTNESS;
+
+const CSV_HEADER: [&str; 20] = [
+    "index",
+    "device",
+    "key",
+    "name",
+    "nickname",
+    "connector",
+    "manufacturer",
+    "model",
+    "product_code",
+    "serial",
+    "year",
+    "width",
+    "height",
+    "left",
+    "top",
+    "primary",
+    "ddc",
+    "mccs_version",
+    "vcp_codes",
+    "supported_modes",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InventoryFormat {
+    Json,
+    Csv,
+}
+
+impl InventoryFormat {
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => InventoryFormat::Csv,
+            _ => InventoryFormat::Json,
+        }
+    }
+}
+
+pub fn default_inventory_path(format: InventoryFormat) -> PathBuf {
+    let base = std::env::var_os("USERPROFILE").map(PathBuf::from).unwrap_or_default();
+    let file = match format {
+        InventoryFormat::Json => "monitor-inventory.json",
+        InventoryFormat::Csv => "monitor-inventory.csv",
+    };
+    base.join("Documents").join(file)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DisplayRecord {
+    pub index: u32,
+    pub device: String,
+    pub key: String,
+    pub name: String,
+    pub nickname: Option<String>,
+    pub connector: Option<String>,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub product_code: Option<String>,
+    pub serial: Option<String>,
+    pub year: Option<u16>,
+    pub resolution: Resolution,
+    pub position: Position,
+    pub primary: bool,
+    pub supported_modes: Vec<String>,
+    pub ddc: bool,
+    pub mccs_version: Option<String>,
+    pub vcp_codes: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DisplayListing {
+    pub index: u32,
+    pub device: String,
+    pub key: String,
+    pub name: String,
+    pub connector: Option<String>,
+    pub nickname: Option<String>,
+    pub label: String,
+    pub width: i32,
+    pub height: i32,
+    pub left: i32,
+    pub top: i32,
+    pub primary: bool,
+}
+
+// Versão leve do inventário, sem EDID nem DDC/CI.
+pub fn list_inventory(nicknames: &BTreeMap<String, String>) -> Result<Vec<DisplayListing>> {
+    listings(&SystemDisplays, nicknames)
+}
+
+fn listings(backend: &impl DisplayBackend, nicknames: &BTreeMap<String, String>) -> Result<Vec<DisplayListing>> {
+    let mut listings = Vec::new();
+    for (display, props) in connected_displays(backend)? {
+        let nickname = nicknames.get(&display.key);
+        listings.push(DisplayListing {
+            index: display.index,
+            label: display.label(nickname.map(String::as_str)),
+            name: display.friendly_name,
+            connector: display.connector.map(tr),
+            nickname: nickname.cloned(),
+            width: props.resolution.w,
+            height: props.resolution.h,
+            left: props.position.left,
+            top: props.position.top,
+            primary: props.is_primary,
+            device: display.name,
+            key: display.key,
+        });
+    }
+    Ok(listings)
+}
+
+pub fn find_display(selector: &str, nicknames: &BTreeMap<String, String>) -> Result<Option<DisplaySummary>> {
+    Ok(list_displays()?
+        .into_iter()
+        .find(|d| d.matches(selector, nicknames.get(&d.key).map(String::as_str))))
+}
+
+// Monitores desconectados durante a leitura ficam fora do inventário.
+pub fn collect_inventory(nicknames: &BTreeMap<String, String>) -> Result<Vec<DisplayRecord>> {
+    Ok(connected_displays(&SystemDisplays)?
+        .into_iter()
+        .map(|(display, props)| record(display, props, nicknames))
+        .collect())
+}
+
+pub fn display_record(display: DisplaySummary, nicknames: &BTreeMap<String, String>) -> Result<DisplayRecord> {
+    let props = find_properties(display.index)?;
+    Ok(record(display, props, nicknames))
+}
+
+fn record(display: DisplaySummary, props: DisplayInfo, nicknames: &BTreeMap<String, String>) -> DisplayRecord {
+    let edid = get_edid(display.index).ok();
+    let capabilities = get_capabilities(display.index, PhysicalTarget::All).ok();
+    let ddc = capabilities.is_some() || get_vcp(display.index, PhysicalTarget::All, BRIGHTNESS).is_ok();
+
+    DisplayRecord {
+        index: display.index,
+        name: display.friendly_name.clone(),
+        nickname: nicknames.get(&display.key).cloned(),
+        connector: display.connector.map(tr),
+        manufacturer: edid.as_ref().map(|e| e.manufacturer.clone()),
+        model: edid.as_ref()
+            .and_then(|e| e.name.clone())
+            .or_else(|| capabilities.as_ref().and_then(|c| c.model.clone())),
+        product_code: edid.as_ref().map(|e| format!("{:04X}", e.product_code)),
+        serial: edid.as_ref().and_then(|e| e.serial_text.clone().or(e.serial.map(|s| s.to_string()))),
+        year: edid.as_ref().map(|e| e.year),
+        resolution: props.resolution,
+        position: props.position.into(),
+        primary: props.is_primary,
+        supported_modes: props.supported_resolutions.iter().map(|r| format!("{}x{}", r.sw, r.sh)).collect(),
+        ddc,
+        mccs_version: capabilities.as_ref().and_then(|c| c.mccs_ver.clone()),
+        vcp_codes: capabilities.iter().flat_map(|c| c.vcp.keys()).map(|code| format!("0x{:02X}", code)).collect(),
+        device: display.name,
+        key: display.key,
+    }
+}
+
+pub fn to_json(records: &[DisplayRecord]) -> Result<String> {
+    serde_json::to_string_pretty(records).map_err(|e| windows::core::Error::new(HRESULT::from_win32(0x0D), e.to_string()))
+}
+
+pub fn to_csv(records: &[DisplayRecord]) -> String {
+    let mut out = CSV_HEADER.join(",");
+    out.push_str("\r\n");
+
+    for r in records {
+        let text = |value: &Option<String>| value.clone().unwrap_or_default();
+        let fields = [
+            r.index.to_string(),
+            r.device.clone(),
+            r.key.clone(),
+            r.name.clone(),
+            text(&r.nickname),
+            text(&r.connector),
+            text(&r.manufacturer),
+            text(&r.model),
+            text(&r.product_code),
+            text(&r.serial),
+            r.year.map(|y| y.to_string()).unwrap_or_default(),
+            r.resolution.w.to_string(),
+            r.resolution.h.to_string(),
+            r.position.left.to_string(),
+            r.position.top.to_string(),
+            r.primary.to_string(),
+            r.ddc.to_string(),
+            text(&r.mccs_version),
+            r.vcp_codes.join(" "),
+            r.supported_modes.join(" "),
+        ];
+        let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&line.join(","));
+        out.push_str("\r\n");
+    }
+
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn export_inventory(path: &Path, nicknames: &BTreeMap<String, String>) -> Result<()> {
+    let records = collect_inventory(nicknames)?;
+    let text = match InventoryFormat::from_path(path) {
+        InventoryFormat::Json => to_json(&records)?,
+        InventoryFormat::Csv => to_csv(&records),
+    };
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, text)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::functions::FakeDisplays;
+
+    #[test]
+    fn skips_monitor_that_vanishes_after_enumeration() {
+        let backend = FakeDisplays::new(vec!["DISPLAY1", "DISPLAY2"], vec!["DISPLAY1"]);
+        let listings = listings(&backend, &BTreeMap::new()).unwrap();
+        assert_eq!(listings.len(), 1);
+        assert_eq!(listings[0].device, "DISPLAY1");
+    }
+
+    #[test]
+    fn skips_monitor_whose_index_was_taken_by_another() {
+        // DISPLAY1 saiu: DISPLAY2 agora responde pelo índice 0 e nada pelo 1.
+        let backend = FakeDisplays::new(vec!["DISPLAY1", "DISPLAY2"], vec!["DISPLAY2"]);
+        assert!(listings(&backend, &BTreeMap::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn all_monitors_gone_is_an_empty_list() {
+        let backend = FakeDisplays::new(vec!["DISPLAY1", "DISPLAY2"], Vec::new());
+        assert!(listings(&backend, &BTreeMap::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn keeps_nicknames_by_key() {
+        let backend = FakeDisplays::new(vec!["DISPLAY1"], vec!["DISPLAY1"]);
+        let nicknames = BTreeMap::from([("KEY-DISPLAY1".to_string(), "Mesa".to_string())]);
+        let listings = listings(&backend, &nicknames).unwrap();
+        assert_eq!(listings[0].nickname.as_deref(), Some("Mesa"));
+        assert_eq!(listings[0].key, "KEY-DISPLAY1");
+    }
+}
@@ -0,0 +1,692 @@
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use windows::core::Result;
+
+#[cfg(windows)]
+use std::fs::File;
+#[cfg(windows)]
+use std::os::windows::io::{AsRawHandle, FromRawHandle};
+#[cfg(windows)]
+use windows::Win32::Foundation::HANDLE;
+#[cfg(windows)]
+use windows::Win32::Storage::FileSystem::PIPE_ACCESS_DUPLEX;
+#[cfg(windows)]
+use windows::Win32::System::Pipes::{
+    ConnectNamedPipe,
+    CreateNamedPipeW,
+    DisconnectNamedPipe,
+    PeekNamedPipe,
+    PIPE_READMODE_BYTE,
+    PIPE_REJECT_REMOTE_CLIENTS,
+    PIPE_TYPE_BYTE,
+    PIPE_UNLIMITED_INSTANCES,
+    PIPE_WAIT,
+};
+#[cfg(windows)]
+use windows::core::{HRESULT, PCWSTR};
+
+#[cfg(unix)]
+use std::io::ErrorKind;
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+#[cfg(unix)]
+use windows::core::HRESULT;
+
+use super::functions::{
+    DisplaySummary,
+    PhysicalTarget,
+    display_brightness,
+    display_topology,
+    get_vcp,
+    move_display,
+    set_primary_display,
+    set_resolution,
+    set_vcp,
+};
+use super::i18n::t;
+use super::inventory::{display_record, find_display, list_inventory};
+use super::settings::load_settings;
+use super::vcp::BRIGHTNESS;
+
+const BUFFER_SIZE: u32 = 64 * 1024;
+const MAX_MESSAGE: usize = 1024 * 1024;
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+#[cfg(windows)]
+const ERROR_PIPE_CONNECTED: u32 = 535;
+
+// Códigos padrão do JSON-RPC 2.0 e, a partir de -32000, os da aplicação.
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+const OPERATION_FAILED: i32 = -32000;
+const MONITOR_NOT_FOUND: i32 = -32001;
+const DDC_FAILED: i32 = -32002;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IpcSettings {
+    pub enabled: bool,
+    // Nome do pipe no Windows; no Linux, do socket em $XDG_RUNTIME_DIR.
+    pub pipe_name: String,
+}
+
+impl Default for IpcSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            pipe_name: "DisplayManager".to_string(),
+        }
+    }
+}
+
+struct RpcError {
+    code: i32,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i32, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+
+    fn failed(error: windows::core::Error) -> Self {
+        Self::new(OPERATION_FAILED, error.message())
+    }
+
+    fn ddc(error: windows::core::Error) -> Self {
+        Self::new(DDC_FAILED, error.message())
+    }
+}
+
+#[derive(Default)]
+struct Subscriptions {
+    next_id: u64,
+    senders: Vec<(u64, Sender<Value>)>,
+    watching: bool,
+}
+
+type Subscribers = Arc<Mutex<Subscriptions>>;
+
+#[cfg(windows)]
+pub fn pipe_path(name: &str) -> String {
+    format!(r"\\.\pipe\{}", name)
+}
+
+#[cfg(windows)]
+pub fn start_server(settings: &IpcSettings) -> Result<()> {
+    let path: Vec<u16> = pipe_path(&settings.pipe_name).encode_utf16().chain(std::iter::once(0)).collect();
+    let subscribers = Subscribers::default();
+
+    // A primeira instância é criada aqui para que um nome já em uso vire erro visível.
+    let first = create_pipe(&path)?;
+    thread::spawn(move || accept(path, first, subscribers));
+
+    Ok(())
+}
+
+#[cfg(windows)]
+fn create_pipe(path: &[u16]) -> Result<File> {
+    let handle = unsafe {
+        CreateNamedPipeW(
+            PCWSTR(path.as_ptr()),
+            PIPE_ACCESS_DUPLEX,
+            PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+            PIPE_UNLIMITED_INSTANCES,
+            BUFFER_SIZE,
+            BUFFER_SIZE,
+            0,
+            None,
+        )
+    };
+    if handle.is_invalid() {
+        return Err(windows::core::Error::from_thread());
+    }
+
+    Ok(unsafe { File::from_raw_handle(handle.0) })
+}
+
+#[cfg(windows)]
+fn accept(path: Vec<u16>, first: File, subscribers: Subscribers) {
+    let mut next = Some(first);
+    loop {
+        let pipe = match next.take().map_or_else(|| create_pipe(&path), Ok) {
+            Ok(pipe) => pipe,
+            Err(_) => {
+                thread::sleep(WATCH_INTERVAL);
+                continue;
+            }
+        };
+
+        let connected = unsafe { ConnectNamedPipe(HANDLE(pipe.as_raw_handle()), None) };
+        // O cliente pode ter se conectado entre a criação e o ConnectNamedPipe.
+        if let Err(e) = connected
+            && e.code() != HRESULT::from_win32(ERROR_PIPE_CONNECTED)
+        {
+            continue;
+        }
+
+        let subscribers = subscribers.clone();
+        thread::spawn(move || serve(pipe, subscribers));
+    }
+}
+
+// Pipes síncronos serializam leitura e escrita no mesmo handle, então uma única
+// thread alterna entre ler o que chegou e entregar notificações.
+#[cfg(windows)]
+fn serve(mut pipe: File, subscribers: Subscribers) {
+    let handle = HANDLE(pipe.as_raw_handle());
+    let mut session = Session::new(subscribers);
+
+    loop {
+        let mut available = 0u32;
+        if unsafe { PeekNamedPipe(handle, None, 0, None, Some(&mut available), None) }.is_err() {
+            break;
+        }
+
+        if available > 0 {
+            let mut chunk = vec![0u8; available as usize];
+            match pipe.read(&mut chunk) {
+                Ok(0) | Err(_) => break,
+                Ok(n) if !session.receive(&chunk[..n], &mut pipe) => break,
+                Ok(_) => continue,
+            }
+        }
+
+        if !session.deliver(&mut pipe) {
+            break;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+
+    drop(session);
+    unsafe {
+        let _ = DisconnectNamedPipe(handle);
+    }
+}
+
+#[cfg(unix)]
+pub fn socket_path(name: &str) -> PathBuf {
+    let base = std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from).unwrap_or_else(std::env::temp_dir);
+    base.join(format!("{}.sock", name))
+}
+
+#[cfg(unix)]
+pub fn start_server(settings: &IpcSettings) -> Result<()> {
+    listen(&socket_path(&settings.pipe_name))
+}
+
+#[cfg(unix)]
+fn listen(path: &Path) -> Result<()> {
+    let failed = |e: std::io::Error| windows::core::Error::new(HRESULT::from_win32(0x1F), e.to_string());
+
+    // Um socket que ninguém atende sobrou de uma execução interrompida; um atendido é erro visível.
+    if path.exists() && UnixStream::connect(path).is_err() {
+        std::fs::remove_file(path).map_err(failed)?;
+    }
+    let listener = UnixListener::bind(path).map_err(failed)?;
+    let subscribers = Subscribers::default();
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let subscribers = subscribers.clone();
+            thread::spawn(move || serve(stream, subscribers));
+        }
+    });
+
+    Ok(())
+}
+
+// A leitura com prazo curto faz o papel do PeekNamedPipe: entre uma e outra saem as notificações.
+#[cfg(unix)]
+fn serve(mut stream: UnixStream, subscribers: Subscribers) {
+    if stream.set_read_timeout(Some(POLL_INTERVAL)).is_err() {
+        return;
+    }
+    let mut session = Session::new(subscribers);
+    let mut chunk = vec![0u8; BUFFER_SIZE as usize];
+
+    loop {
+        match stream.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) if !session.receive(&chunk[..n], &mut stream) => break,
+            Ok(_) => continue,
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => break,
+        }
+
+        if !session.deliver(&mut stream) {
+            break;
+        }
+    }
+}
+
+// Estado de uma conexão, o mesmo nos dois transportes.
+struct Session {
+    subscribers: Subscribers,
+    buffer: Vec<u8>,
+    subscribed: bool,
+    subscription: Option<(u64, Receiver<Value>)>,
+}
+
+impl Session {
+    fn new(subscribers: Subscribers) -> Self {
+        Self { subscribers, buffer: Vec::new(), subscribed: false, subscription: None }
+    }
+
+    // Responde cada linha completa; falso encerra a conexão.
+    fn receive(&mut self, chunk: &[u8], out: &mut impl Write) -> bool {
+        self.buffer.extend_from_slice(chunk);
+        while let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            let response = handle_message(&line, &mut self.subscribed);
+            match (self.subscribed, &self.subscription) {
+                (true, None) => self.subscription = subscribe(&self.subscribers),
+                (false, Some((id, _))) => {
+                    unsubscribe(&self.subscribers, *id);
+                    self.subscription = None;
+                }
+                _ => {}
+            }
+            if let Some(response) = response
+                && writeln!(out, "{}", response).is_err()
+            {
+                return false;
+            }
+        }
+        self.buffer.len() <= MAX_MESSAGE
+    }
+
+    fn deliver(&self, out: &mut impl Write) -> bool {
+        match &self.subscription {
+            Some((_, notifications)) => !notifications.try_iter().any(|notification| writeln!(out, "{}", notification).is_err()),
+            None => true,
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        if let Some((id, _)) = &self.subscription {
+            unsubscribe(&self.subscribers, *id);
+        }
+    }
+}
+
+// O primeiro assinante liga a vigilância da topologia; ela se encerra sozinha sem assinantes.
+fn subscribe(subscribers: &Subscribers) -> Option<(u64, Receiver<Value>)> {
+    let (sender, receiver) = mpsc::channel();
+    let mut list = subscribers.lock().ok()?;
+    list.next_id += 1;
+    let id = list.next_id;
+    list.senders.push((id, sender));
+    if !list.watching {
+        list.watching = true;
+        let watched = subscribers.clone();
+        thread::spawn(move || watch(watched));
+    }
+    Some((id, receiver))
+}
+
+fn unsubscribe(subscribers: &Subscribers, id: u64) {
+    if let Ok(mut list) = subscribers.lock() {
+        list.senders.retain(|(i, _)| *i != id);
+    }
+}
+
+fn watch(subscribers: Subscribers) {
+    // Só a topologia é consultada a cada volta; o inventário completo, apenas quando ela muda.
+    let mut last = display_topology();
+    loop {
+        thread::sleep(WATCH_INTERVAL);
+        match subscribers.lock() {
+            Ok(mut list) if list.senders.is_empty() => {
+                list.watching = false;
+                return;
+            }
+            Ok(_) => {}
+            Err(_) => return,
+        }
+
+        let topology = display_topology();
+        if topology == last {
+            continue;
+        }
+        last = topology;
+
+        let Ok(displays) = list_inventory(&load_settings().nicknames) else {
+            continue;
+        };
+        let notification = json!({
+            "jsonrpc": "2.0",
+            "method": "displays_changed",
+            "params": { "displays": displays },
+        });
+        if let Ok(mut list) = subscribers.lock() {
+            list.senders.retain(|(_, sender)| sender.send(notification.clone()).is_ok());
+        }
+    }
+}
+
+fn handle_message(line: &[u8], subscribed: &mut bool) -> Option<Value> {
+    let message: Value = match serde_json::from_slice(line) {
+        Ok(message) => message,
+        Err(e) => return Some(error_response(Value::Null, RpcError::new(PARSE_ERROR, e.to_string()))),
+    };
+
+    match message {
+        Value::Array(batch) if batch.is_empty() => {
+            Some(error_response(Value::Null, RpcError::new(INVALID_REQUEST, t!("rpc-empty-batch"))))
+        }
+        Value::Array(batch) => {
+            let responses: Vec<Value> = batch.iter().filter_map(|request| handle_request(request, subscribed)).collect();
+            (!responses.is_empty()).then_some(Value::Array(responses))
+        }
+        request => handle_request(&request, subscribed),
+    }
+}
+
+fn handle_request(request: &Value, subscribed: &mut bool) -> Option<Value> {
+    let id = request.get("id").cloned();
+    let method = request.get("method").and_then(Value::as_str);
+    let (Some(method), Some("2.0")) = (method, request.get("jsonrpc").and_then(Value::as_str)) else {
+        return Some(error_response(id.unwrap_or(Value::Null), RpcError::new(INVALID_REQUEST, t!("rpc-invalid-request"))));
+    };
+
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+    let result = call(method, &params, subscribed);
+
+    // Requisições sem id são notificações e não recebem resposta.
+    let id = id?;
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => error_response(id, e),
+    })
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+// Os parâmetros são validados antes de procurar o monitor, que exige enumerar o hardware.
+fn call(method: &str, params: &Value, subscribed: &mut bool) -> std::result::Result<Value, RpcError> {
+    match method {
+        "list" => Ok(json!(list_inventory(&load_settings().nicknames).map_err(RpcError::failed)?)),
+        "info" => {
+            let selector = selector(params)?;
+            let nicknames = load_settings().nicknames;
+            let display = find_monitor(&selector, &nicknames)?;
+            Ok(json!(display_record(display, &nicknames).map_err(RpcError::failed)?))
+        }
+        "set_mode" => {
+            let (width, height) = (number(params, "width")?, number(params, "height")?);
+            let display = monitor(params)?;
+            set_resolution(display.index, width, height).map_err(RpcError::failed)?;
+            Ok(Value::Null)
+        }
+        "move" => {
+            let (x, y) = (number(params, "x")?, number(params, "y")?);
+            let display = monitor(params)?;
+            move_display(display.index, x, y).map_err(RpcError::failed)?;
+            Ok(Value::Null)
+        }
+        "set_primary" => {
+            let display = monitor(params)?;
+            set_primary_display(display.index).map_err(RpcError::failed)?;
+            Ok(Value::Null)
+        }
+        "get_brightness" => {
+            let physical = physical(params)?;
+            let display = monitor(params)?;
+            let value = get_vcp(display.index, physical, BRIGHTNESS).map_err(RpcError::ddc)?;
+            Ok(json!({ "brightness": value.percent(), "current": value.current, "max": value.max }))
+        }
+        "set_brightness" => {
+            let (physical, percent): (_, u32) = (physical(params)?, number(params, "value")?);
+            if percent > 100 {
+                return Err(RpcError::new(INVALID_PARAMS, t!("brightness-out-of-range")));
+            }
+            let display = monitor(params)?;
+            display_brightness(display.index, physical, percent).map_err(RpcError::ddc)?;
+            Ok(Value::Null)
+        }
+        "get_vcp" => {
+            let (physical, code) = (physical(params)?, code(params)?);
+            let display = monitor(params)?;
+            let value = get_vcp(display.index, physical, code).map_err(RpcError::ddc)?;
+            Ok(json!({ "current": value.current, "max": value.max }))
+        }
+        "set_vcp" => {
+            let (physical, code, value) = (physical(params)?, code(params)?, number(params, "value")?);
+            let display = monitor(params)?;
+            set_vcp(display.index, physical, code, value).map_err(RpcError::ddc)?;
+            Ok(Value::Null)
+        }
+        "subscribe" => {
+            *subscribed = true;
+            Ok(Value::Bool(true))
+        }
+        "unsubscribe" => {
+            *subscribed = false;
+            Ok(Value::Bool(true))
+        }
+        _ => Err(RpcError::new(METHOD_NOT_FOUND, t!("rpc-method-not-found", method = method))),
+    }
+}
+
+fn selector(params: &Value) -> std::result::Result<String, RpcError> {
+    match params.get("monitor") {
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(Value::Number(n)) => Ok(n.to_string()),
+        _ => Err(RpcError::new(INVALID_PARAMS, t!("rpc-missing-monitor"))),
+    }
+}
+
+fn monitor(params: &Value) -> std::result::Result<DisplaySummary, RpcError> {
+    let selector = selector(params)?;
+    find_monitor(&selector, &load_settings().nicknames)
+}
+
+fn find_monitor(selector: &str, nicknames: &BTreeMap<String, String>) -> std::result::Result<DisplaySummary, RpcError> {
+    find_display(selector, nicknames)
+        .map_err(RpcError::failed)?
+        .ok_or_else(|| RpcError::new(MONITOR_NOT_FOUND, t!("monitor-not-found", monitor = selector)))
+}
+
+fn number<T: TryFrom<i64>>(params: &Value, key: &str) -> std::result::Result<T, RpcError> {
+    params
+        .get(key)
+        .and_then(Value::as_i64)
+        .and_then(|n| T::try_from(n).ok())
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, t!("rpc-invalid-param", key = key)))
+}
+
+fn physical(params: &Value) -> std::result::Result<PhysicalTarget, RpcError> {
+    match params.get("physical") {
+        None | Some(Value::Null) => Ok(PhysicalTarget::All),
+        Some(_) => Ok(PhysicalTarget::One(number(params, "physical")?)),
+    }
+}
+
+// Aceita número ou texto hexadecimal ("0x10").
+fn code(params: &Value) -> std::result::Result<u8, RpcError> {
+    match params.get("code") {
+        Some(Value::String(s)) => {
+            let hex = s.trim().trim_start_matches("0x").trim_start_matches("0X");
+            u8::from_str_radix(hex, 16).map_err(|_| RpcError::new(INVALID_PARAMS, t!("invalid-vcp-code", code = s.as_str())))
+        }
+        _ => number(params, "code"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn send(message: &str, subscribed: &mut bool) -> Option<Value> {
+        handle_message(message.as_bytes(), subscribed)
+    }
+
+    fn error_code(response: &Value) -> Option<i64> {
+        response["error"]["code"].as_i64()
+    }
+
+    #[test]
+    fn subscribes_and_unsubscribes() {
+        let mut subscribed = false;
+        let response = send(r#"{"jsonrpc":"2.0","id":1,"method":"subscribe"}"#, &mut subscribed).unwrap();
+        assert_eq!(response, json!({ "jsonrpc": "2.0", "id": 1, "result": true }));
+        assert!(subscribed);
+
+        let response = send(r#"{"jsonrpc":"2.0","id":"b","method":"unsubscribe"}"#, &mut subscribed).unwrap();
+        assert_eq!(response["id"], "b");
+        assert!(!subscribed);
+    }
+
+    #[test]
+    fn notifications_get_no_response() {
+        let mut subscribed = false;
+        assert_eq!(send(r#"{"jsonrpc":"2.0","method":"subscribe"}"#, &mut subscribed), None);
+        assert!(subscribed);
+        assert_eq!(send(r#"[{"jsonrpc":"2.0","method":"unsubscribe"}]"#, &mut subscribed), None);
+        assert!(!subscribed);
+    }
+
+    #[test]
+    fn answers_batches() {
+        let mut subscribed = false;
+        let batch = r#"[{"jsonrpc":"2.0","id":1,"method":"subscribe"},{"jsonrpc":"2.0","id":2,"method":"nope"}]"#;
+        let response = send(batch, &mut subscribed).unwrap();
+        let responses = response.as_array().unwrap();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["result"], true);
+        assert_eq!(error_code(&responses[1]), Some(METHOD_NOT_FOUND as i64));
+
+        let response = send("[]", &mut subscribed).unwrap();
+        assert_eq!(error_code(&response), Some(INVALID_REQUEST as i64));
+    }
+
+    #[test]
+    fn rejects_unknown_method() {
+        let response = send(r#"{"jsonrpc":"2.0","id":7,"method":"format_disk"}"#, &mut false).unwrap();
+        assert_eq!(response["id"], 7);
+        assert_eq!(error_code(&response), Some(METHOD_NOT_FOUND as i64));
+    }
+
+    #[test]
+    fn rejects_malformed_json() {
+        for message in [r#"{"jsonrpc":"2.0","id":1,"method":"list""#, "not json", "{\"id\": 1,}"] {
+            let response = send(message, &mut false).unwrap();
+            assert_eq!(response["id"], Value::Null);
+            assert_eq!(error_code(&response), Some(PARSE_ERROR as i64));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_requests() {
+        for message in [r#"{"id":1,"method":"list"}"#, r#"{"jsonrpc":"1.0","id":1,"method":"list"}"#, r#"{"jsonrpc":"2.0","id":1}"#, "42"] {
+            let response = send(message, &mut false).unwrap();
+            assert_eq!(error_code(&response), Some(INVALID_REQUEST as i64), "{message}");
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_params() {
+        // Todos falham antes de procurar o monitor.
+        for request in [
+            json!({ "method": "set_primary", "params": {} }),
+            json!({ "method": "info", "params": { "monitor": true } }),
+            json!({ "method": "set_mode", "params": { "monitor": 0, "width": "wide", "height": 1080 } }),
+            json!({ "method": "move", "params": { "monitor": 0, "x": 1.5, "y": 0 } }),
+            json!({ "method": "set_brightness", "params": { "monitor": 0, "value": 150 } }),
+            json!({ "method": "set_brightness", "params": { "monitor": 0, "value": -1 } }),
+            json!({ "method": "get_brightness", "params": { "monitor": 0, "physical": "first" } }),
+            json!({ "method": "get_vcp", "params": { "monitor": 0, "code": "0xZZ" } }),
+            json!({ "method": "set_vcp", "params": { "monitor": 0, "code": 300, "value": 1 } }),
+            json!({ "method": "set_vcp", "params": { "monitor": 0, "code": "10" } }),
+        ] {
+            let mut request = request;
+            request["jsonrpc"] = json!("2.0");
+            request["id"] = json!(1);
+            let response = send(&request.to_string(), &mut false).unwrap();
+            assert_eq!(error_code(&response), Some(INVALID_PARAMS as i64), "{request}");
+        }
+    }
+
+    #[cfg(unix)]
+    fn socket_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("display-manager-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn answers_over_a_unix_socket() {
+        use std::io::{BufRead, BufReader};
+
+        let dir = socket_dir("ipc");
+        let path = dir.join("ipc.sock");
+        listen(&path).unwrap();
+        // Com o servidor atendendo, um segundo no mesmo caminho falha em vez de roubar o socket.
+        assert!(listen(&path).is_err());
+
+        let mut client = UnixStream::connect(&path).unwrap();
+        client.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let mut reader = BufReader::new(client.try_clone().unwrap());
+        let mut reply = || {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            serde_json::from_str::<Value>(&line).unwrap()
+        };
+
+        writeln!(client, r#"{{"jsonrpc":"2.0","id":1,"method":"format_disk"}}"#).unwrap();
+        let response = reply();
+        assert_eq!(response["id"], 1);
+        assert_eq!(error_code(&response), Some(METHOD_NOT_FOUND as i64));
+
+        // Notificações não têm resposta; a requisição seguinte chega em dois pedaços.
+        writeln!(client, r#"{{"jsonrpc":"2.0","method":"unsubscribe"}}"#).unwrap();
+        write!(client, r#"{{"jsonrpc":"2.0","id":2,"#).unwrap();
+        client.flush().unwrap();
+        thread::sleep(POLL_INTERVAL * 2);
+        writeln!(client, r#""method":"unsubscribe"}}"#).unwrap();
+        assert_eq!(reply(), json!({ "jsonrpc": "2.0", "id": 2, "result": true }));
+
+        writeln!(client, "not json").unwrap();
+        assert_eq!(error_code(&reply()), Some(PARSE_ERROR as i64));
+
+        drop(client);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn replaces_a_stale_socket() {
+        let dir = socket_dir("stale");
+        let path = dir.join("ipc.sock");
+        // Sobra de um processo encerrado: o arquivo existe, mas ninguém atende.
+        drop(UnixListener::bind(&path).unwrap());
+        assert!(path.exists());
+
+        listen(&path).unwrap();
+        assert!(UnixStream::connect(&path).is_ok());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
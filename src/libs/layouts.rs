@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use windows::core::Result;
+
+use super::functions::{
+    find_properties,
+    list_displays,
+    move_display,
+    set_primary_display,
+    set_resolution,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayoutEntry {
+    pub monitor: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub primary: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Layout {
+    pub name: String,
+    pub monitors: Vec<LayoutEntry>,
+}
+
+pub fn capture_layout(name: &str) -> Result<Layout> {
+    let monitors = list_displays()?
+        .into_iter()
+        .filter_map(|display| {
+            // Um monitor desconectado durante a leitura fica de fora.
+            let props = find_properties(display.index).ok()?;
+            Some(LayoutEntry {
+                monitor: display.key,
+                x: props.position.left,
+                y: props.position.top,
+                width: props.resolution.w as u32,
+                height: props.resolution.h as u32,
+                primary: props.is_primary,
+            })
+        })
+        .collect();
+
+    Ok(Layout { name: name.to_string(), monitors })
+}
+
+// Os índices seguem a ordem de enumeração, que muda quando o principal muda.
+fn index_of(monitor: &str) -> Result<Option<u32>> {
+    Ok(list_displays()?.into_iter().find(|d| d.key == monitor).map(|d| d.index))
+}
+
+pub fn apply_layout(layout: &Layout) -> Result<()> {
+    let mut result = Ok(());
+
+    // Resolução primeiro, depois o principal (que define a origem) e por fim as posições,
+    // já relativas ao principal. Monitores ausentes são ignorados.
+    for entry in &layout.monitors {
+        let Some(index) = index_of(&entry.monitor)? else {
+            continue;
+        };
+        let props = find_properties(index)?;
+        if (props.resolution.w as u32, props.resolution.h as u32) != (entry.width, entry.height)
+            && let Err(e) = set_resolution(index, entry.width, entry.height)
+        {
+            result = Err(e);
+        }
+    }
+    if let Some(entry) = layout.monitors.iter().find(|e| e.primary)
+        && let Some(index) = index_of(&entry.monitor)?
+        && let Err(e) = set_primary_display(index)
+    {
+        result = Err(e);
+    }
+    for entry in layout.monitors.iter().filter(|e| !e.primary) {
+        let Some(index) = index_of(&entry.monitor)? else {
+            continue;
+        };
+        let props = find_properties(index)?;
+        if (props.position.left, props.position.top) != (entry.x, entry.y)
+            && let Err(e) = move_display(index, entry.x, entry.y)
+        {
+            result = Err(e);
+        }
+    }
+
+    result
+}
@@ -0,0 +1,111 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+const ERROR_TOAST_DURATION: Duration = Duration::from_secs(10);
+const LOG_LIMIT: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Success,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Notice {
+    pub severity: Severity,
+    pub message: String,
+    pub monitor: Option<String>,
+    pub time: DateTime<Local>,
+    // Quantas vezes a mesma mensagem chegou seguida.
+    pub count: u32,
+    expires: Instant,
+}
+
+impl Notice {
+    pub fn remaining(&self) -> Duration {
+        self.expires.saturating_duration_since(Instant::now())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Notifications {
+    toasts: Vec<Notice>,
+    log: VecDeque<Notice>,
+}
+
+impl Notifications {
+    pub fn success(&mut self, message: String) {
+        self.push(Severity::Success, None, message);
+    }
+
+    pub fn warning(&mut self, monitor: Option<String>, message: String) {
+        self.push(Severity::Warning, monitor, message);
+    }
+
+    pub fn error(&mut self, monitor: Option<String>, message: String) {
+        self.push(Severity::Error, monitor, message);
+    }
+
+    fn push(&mut self, severity: Severity, monitor: Option<String>, message: String) {
+        let duration = match severity {
+            Severity::Success => TOAST_DURATION,
+            Severity::Warning | Severity::Error => ERROR_TOAST_DURATION,
+        };
+        let notice = Notice {
+            severity,
+            message,
+            monitor,
+            time: Local::now(),
+            count: 1,
+            expires: Instant::now() + duration,
+        };
+
+        if severity != Severity::Success {
+            match self.log.back_mut() {
+                Some(last) if last.message == notice.message && last.monitor == notice.monitor => {
+                    last.count += 1;
+                    last.time = notice.time;
+                }
+                _ => {
+                    self.log.push_back(notice.clone());
+                    if self.log.len() > LOG_LIMIT {
+                        self.log.pop_front();
+                    }
+                }
+            }
+        }
+
+        // Repetições renovam o aviso já visível em vez de empilhar outro igual.
+        match self.toasts.iter_mut().find(|t| t.message == notice.message && t.monitor == notice.monitor) {
+            Some(toast) => {
+                toast.count += 1;
+                toast.expires = notice.expires;
+            }
+            None => self.toasts.push(notice),
+        }
+    }
+
+    pub fn toasts(&mut self) -> &[Notice] {
+        let now = Instant::now();
+        self.toasts.retain(|t| t.expires > now);
+        &self.toasts
+    }
+
+    pub fn dismiss(&mut self, index: usize) {
+        if index < self.toasts.len() {
+            self.toasts.remove(index);
+        }
+    }
+
+    pub fn log(&self) -> &VecDeque<Notice> {
+        &self.log
+    }
+
+    pub fn clear_log(&mut self) {
+        self.log.clear();
+    }
+}
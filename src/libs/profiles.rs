@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use windows::core::Result;
+
+use super::functions::{
+    PhysicalTarget,
+    display_brightness,
+    get_input_source,
+    get_vcp,
+    list_displays,
+    set_input_source,
+};
+use super::vcp::BRIGHTNESS;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileEntry {
+    pub monitor: String,
+    pub brightness: Option<u32>,
+    pub input_source: Option<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub monitors: Vec<ProfileEntry>,
+}
+
+pub fn capture_profile(name: &str) -> Result<Profile> {
+    let monitors = list_displays()?
+        .into_iter()
+        .map(|display| ProfileEntry {
+            brightness: get_vcp(display.index, PhysicalTarget::All, BRIGHTNESS).ok().map(|v| v.percent()),
+            input_source: get_input_source(display.index, PhysicalTarget::All).ok(),
+            monitor: display.key,
+        })
+        .filter(|entry| entry.brightness.is_some() || entry.input_source.is_some())
+        .collect();
+
+    Ok(Profile { name: name.to_string(), monitors })
+}
+
+pub fn apply_profile(profile: &Profile) -> Result<()> {
+    let displays = list_displays()?;
+    let mut result = Ok(());
+
+    // Monitores ausentes são ignorados; os demais recebem o perfil mesmo se um falhar.
+    for entry in &profile.monitors {
+        let Some(display) = displays.iter().find(|d| d.key == entry.monitor) else {
+            continue;
+        };
+        if let Some(brightness) = entry.brightness
+            && let Err(e) = display_brightness(display.index, PhysicalTarget::All, brightness)
+        {
+            result = Err(e);
+        }
+        // Só troca a entrada se for diferente, para não derrubar o sinal à toa.
+        if let Some(source) = entry.input_source
+            && get_input_source(display.index, PhysicalTarget::All).ok() != Some(source)
+            && let Err(e) = set_input_source(display.index, PhysicalTarget::All, source)
+        {
+            result = Err(e);
+        }
+    }
+
+    result
+}
@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use chrono::{Datelike, Local, Timelike};
+use serde::{Deserialize, Serialize};
+
+use super::brightness::BrightnessController;
+use super::functions::{PhysicalTarget, get_vcp, list_displays, set_vcp};
+use super::groups::{BrightnessGroup, CurvePoint, group_of, invert_level};
+use super::vcp::{BRIGHTNESS, COLOR_PRESET};
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AnchorTime {
+    Fixed { minute: u32 },
+    Sunrise { offset: i32 },
+    Sunset { offset: i32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Anchor {
+    pub time: AnchorTime,
+    pub brightness: u32,
+    pub color_preset: Option<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonitorSchedule {
+    pub monitor: String,
+    pub anchors: Vec<Anchor>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScheduleSettings {
+    pub enabled: bool,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub fade_seconds: u32,
+    pub monitors: Vec<MonitorSchedule>,
+}
+
+impl Default for ScheduleSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            latitude: -23.55,
+            longitude: -46.63,
+            fade_seconds: 10,
+            monitors: Vec::new(),
+        }
+    }
+}
+
+impl ScheduleSettings {
+    pub fn anchors_mut(&mut self, monitor: &str) -> &mut Vec<Anchor> {
+        let position = match self.monitors.iter().position(|m| m.monitor == monitor) {
+            Some(position) => position,
+            None => {
+                self.monitors.push(MonitorSchedule { monitor: monitor.to_string(), anchors: Vec::new() });
+                self.monitors.len() - 1
+            }
+        };
+        &mut self.monitors[position].anchors
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SunTimes {
+    pub sunrise: Option<u32>,
+    pub sunset: Option<u32>,
+}
+
+// Algoritmo do "Almanac for Computers" (1990), com precisão de ~1 minuto.
+pub fn sun_times(day_of_year: u32, latitude: f64, longitude: f64, utc_offset_minutes: i32) -> SunTimes {
+    SunTimes {
+        sunrise: sun_event(day_of_year, latitude, longitude, utc_offset_minutes, true),
+        sunset: sun_event(day_of_year, latitude, longitude, utc_offset_minutes, false),
+    }
+}
+
+fn sun_event(day_of_year: u32, latitude: f64, longitude: f64, utc_offset_minutes: i32, rising: bool) -> Option<u32> {
+    let rad = PI / 180.0;
+    let zenith: f64 = 90.833;
+    let lng_hour = longitude / 15.0;
+    let t = day_of_year as f64 + ((if rising { 6.0 } else { 18.0 }) - lng_hour) / 24.0;
+
+    let m = 0.9856 * t - 3.289;
+    let l = (m + 1.916 * (m * rad).sin() + 0.020 * (2.0 * m * rad).sin() + 282.634).rem_euclid(360.0);
+
+    let mut ra = ((0.91764 * (l * rad).tan()).atan() / rad).rem_euclid(360.0);
+    ra += (l / 90.0).floor() * 90.0 - (ra / 90.0).floor() * 90.0;
+    ra /= 15.0;
+
+    let sin_dec = 0.39782 * (l * rad).sin();
+    let cos_dec = sin_dec.asin().cos();
+    let cos_h = ((zenith * rad).cos() - sin_dec * (latitude * rad).sin()) / (cos_dec * (latitude * rad).cos());
+    if !cos_h.is_finite() || !(-1.0..=1.0).contains(&cos_h) {
+        return None;
+    }
+
+    let h = if rising { 360.0 - cos_h.acos() / rad } else { cos_h.acos() / rad } / 15.0;
+    let local_t = h + ra - 0.06571 * t - 6.622;
+    let utc = (local_t - lng_hour).rem_euclid(24.0);
+    let minutes = (utc * 60.0).round() as i64 + utc_offset_minutes as i64;
+
+    Some(minutes.rem_euclid(MINUTES_PER_DAY) as u32)
+}
+
+pub fn resolve(time: AnchorTime, sun: SunTimes) -> Option<u32> {
+    let shifted = |base: Option<u32>, offset: i32| {
+        base.map(|b| (b as i64 + offset as i64).rem_euclid(MINUTES_PER_DAY) as u32)
+    };
+
+    match time {
+        AnchorTime::Fixed { minute } => Some(minute.min(MINUTES_PER_DAY as u32 - 1)),
+        AnchorTime::Sunrise { offset } => shifted(sun.sunrise, offset),
+        AnchorTime::Sunset { offset } => shifted(sun.sunset, offset),
+    }
+}
+
+pub fn point_at(anchors: &[Anchor], sun: SunTimes, minute: u32) -> Option<(u32, Option<u8>)> {
+    let mut points: Vec<(i64, &Anchor)> = anchors
+        .iter()
+        .filter_map(|a| resolve(a.time, sun).map(|m| (m as i64, a)))
+        .collect();
+    points.sort_by_key(|(m, _)| *m);
+
+    let minute = minute as i64;
+    let (prev_m, prev) = *points.iter().rev().find(|(m, _)| *m <= minute).or(points.last())?;
+    let (next_m, next) = *points.iter().find(|(m, _)| *m > minute).or(points.first())?;
+
+    // A curva é circular: depois do último ponto do dia volta ao primeiro.
+    let span = match (next_m - prev_m).rem_euclid(MINUTES_PER_DAY) {
+        0 => MINUTES_PER_DAY,
+        span => span,
+    };
+    let elapsed = (minute - prev_m).rem_euclid(MINUTES_PER_DAY);
+    let from = prev.brightness.min(100) as f64;
+    let to = next.brightness.min(100) as f64;
+    let brightness = from + (to - from) * elapsed as f64 / span as f64;
+
+    Some((brightness.round() as u32, prev.color_preset))
+}
+
+// Como no slider, o nível agendado vale para o grupo inteiro, cada membro pela sua curva.
+// Membros com agendamento próprio seguem o deles; os demais, o primeiro agendamento do grupo.
+fn followers<'a>(monitor: &'a str, groups: &'a [BrightnessGroup], schedules: &[MonitorSchedule]) -> Vec<(&'a str, &'a [CurvePoint])> {
+    let Some(group) = group_of(groups, monitor) else {
+        return vec![(monitor, &[])];
+    };
+    let scheduled = |member: &str| schedules.iter().any(|s| s.monitor == member);
+    let leader = schedules.iter().find(|s| group.member(&s.monitor).is_some()).is_some_and(|s| s.monitor == monitor);
+    group.members
+        .iter()
+        .filter(|m| m.monitor == monitor || (leader && !scheduled(&m.monitor)))
+        .map(|m| (m.monitor.as_str(), m.curve.as_slice()))
+        .collect()
+}
+
+pub struct Scheduler {
+    settings: Arc<Mutex<(ScheduleSettings, Vec<BrightnessGroup>)>>,
+}
+
+impl Scheduler {
+    pub fn new(settings: ScheduleSettings, groups: Vec<BrightnessGroup>) -> Self {
+        let settings = Arc::new(Mutex::new((settings, groups)));
+        let shared = settings.clone();
+        thread::spawn(move || run(shared));
+        Self { settings }
+    }
+
+    pub fn update(&self, settings: ScheduleSettings, groups: Vec<BrightnessGroup>) {
+        if let Ok(mut current) = self.settings.lock() {
+            *current = (settings, groups);
+        }
+    }
+}
+
+fn run(settings: Arc<Mutex<(ScheduleSettings, Vec<BrightnessGroup>)>>) {
+    let controller = BrightnessController::default();
+    let mut applied: HashMap<String, (u32, Option<u8>)> = HashMap::new();
+
+    loop {
+        let (settings, groups) = match settings.lock() {
+            Ok(settings) => settings.clone(),
+            Err(_) => return,
+        };
+
+        if settings.enabled {
+            let now = Local::now();
+            let offset = now.offset().local_minus_utc() / 60;
+            let sun = sun_times(now.ordinal(), settings.latitude, settings.longitude, offset);
+            let minute = now.hour() * 60 + now.minute();
+            let displays = list_displays().unwrap_or_default();
+
+            for schedule in &settings.monitors {
+                let Some(display) = displays.iter().find(|d| d.key == schedule.monitor) else {
+                    continue;
+                };
+                let Some((brightness, preset)) = point_at(&schedule.anchors, sun, minute) else {
+                    continue;
+                };
+
+                let previous = applied.get(&schedule.monitor).copied();
+                if previous.map(|(b, _)| b) != Some(brightness) {
+                    for (monitor, curve) in followers(&schedule.monitor, &groups, &settings.monitors) {
+                        let Some(member) = displays.iter().find(|d| d.key == monitor) else {
+                            continue;
+                        };
+                        // Sem transição anterior, parte do nível atual, lido de volta pela curva.
+                        let from = match previous {
+                            Some((b, _)) => b,
+                            None => get_vcp(member.index, PhysicalTarget::All, BRIGHTNESS)
+                                .ok()
+                                .and_then(|v| if curve.is_empty() { Some(v.percent()) } else { invert_level(curve, v.current) })
+                                .unwrap_or(brightness),
+                        };
+                        controller.fade_curve(member.index, PhysicalTarget::All, from, brightness, Duration::from_secs(settings.fade_seconds as u64), curve);
+                    }
+                }
+                if let Some(preset) = preset
+                    && previous.and_then(|(_, p)| p) != Some(preset)
+                {
+                    let _ = set_vcp(display.index, PhysicalTarget::All, COLOR_PRESET, preset as u32);
+                }
+
+                applied.insert(schedule.monitor.clone(), (brightness, preset));
+            }
+        } else {
+            applied.clear();
+        }
+
+        thread::sleep(CHECK_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::groups::GroupMember;
+
+    fn hm(hour: u32, minute: u32) -> u32 {
+        hour * 60 + minute
+    }
+
+    fn assert_near(actual: Option<u32>, expected: u32) {
+        let actual = actual.expect("evento do sol ausente");
+        let diff = (actual as i64 - expected as i64).rem_euclid(MINUTES_PER_DAY);
+        assert!(diff.min(MINUTES_PER_DAY - diff) <= 2, "{actual} longe de {expected}");
+    }
+
+    fn anchor(time: AnchorTime, brightness: u32, color_preset: Option<u8>) -> Anchor {
+        Anchor { time, brightness, color_preset }
+    }
+
+    const NO_SUN: SunTimes = SunTimes { sunrise: None, sunset: None };
+
+    #[test]
+    fn known_sun_times() {
+        // Horários publicados para 21/06/2024 (dia 173) e 21/12/2024 (dia 356).
+        let sao_paulo = sun_times(173, -23.55, -46.63, -180);
+        assert_near(sao_paulo.sunrise, hm(6, 47));
+        assert_near(sao_paulo.sunset, hm(17, 28));
+
+        let london = sun_times(173, 51.51, -0.13, 60);
+        assert_near(london.sunrise, hm(4, 43));
+        assert_near(london.sunset, hm(21, 21));
+        let london = sun_times(356, 51.51, -0.13, 0);
+        assert_near(london.sunrise, hm(8, 4));
+        assert_near(london.sunset, hm(15, 53));
+
+        // Em Reykjavik o pôr do sol de junho cai depois da meia-noite.
+        let reykjavik = sun_times(173, 64.15, -21.94, 0);
+        assert_near(reykjavik.sunrise, hm(2, 55));
+        assert_near(reykjavik.sunset, hm(0, 3));
+    }
+
+    #[test]
+    fn polar_day_and_night() {
+        assert_eq!(sun_times(172, 69.65, 18.96, 120), NO_SUN);
+        assert_eq!(sun_times(355, 69.65, 18.96, 60), NO_SUN);
+        assert_eq!(sun_times(172, -77.85, 166.67, 720), NO_SUN);
+
+        // Âncoras do sol somem e o agendamento fica só com as fixas.
+        assert_eq!(resolve(AnchorTime::Sunset { offset: -30 }, NO_SUN), None);
+        let anchors = [anchor(AnchorTime::Sunrise { offset: 0 }, 80, None), anchor(AnchorTime::Sunset { offset: 0 }, 20, None)];
+        assert_eq!(point_at(&anchors, NO_SUN, hm(12, 0)), None);
+        let anchors = [anchors[0], anchor(AnchorTime::Fixed { minute: hm(9, 0) }, 60, None)];
+        assert_eq!(point_at(&anchors, NO_SUN, hm(12, 0)), Some((60, None)));
+    }
+
+    #[test]
+    fn resolve_wraps_offsets_around_the_day() {
+        let sun = SunTimes { sunrise: Some(hm(0, 10)), sunset: Some(hm(23, 50)) };
+        assert_eq!(resolve(AnchorTime::Sunrise { offset: -30 }, sun), Some(hm(23, 40)));
+        assert_eq!(resolve(AnchorTime::Sunset { offset: 30 }, sun), Some(hm(0, 20)));
+        assert_eq!(resolve(AnchorTime::Fixed { minute: 5000 }, sun), Some(hm(23, 59)));
+    }
+
+    #[test]
+    fn interpolates_across_midnight() {
+        let anchors = [
+            anchor(AnchorTime::Fixed { minute: hm(6, 0) }, 80, None),
+            anchor(AnchorTime::Fixed { minute: hm(22, 0) }, 20, Some(4)),
+        ];
+        // Das 22h às 6h são 8 horas indo de 20 a 80, passando pela meia-noite.
+        assert_eq!(point_at(&anchors, NO_SUN, hm(22, 0)), Some((20, Some(4))));
+        assert_eq!(point_at(&anchors, NO_SUN, hm(0, 0)), Some((35, Some(4))));
+        assert_eq!(point_at(&anchors, NO_SUN, hm(2, 0)), Some((50, Some(4))));
+        assert_eq!(point_at(&anchors, NO_SUN, hm(6, 0)), Some((80, None)));
+        assert_eq!(point_at(&anchors, NO_SUN, hm(14, 0)), Some((50, None)));
+
+        // Um único ponto vale o dia todo.
+        assert_eq!(point_at(&anchors[..1], NO_SUN, hm(3, 0)), Some((80, None)));
+        assert_eq!(point_at(&[], NO_SUN, hm(3, 0)), None);
+    }
+
+    #[test]
+    fn followers_use_group_curves() {
+        let curve = vec![CurvePoint { level: 0, value: 10 }, CurvePoint { level: 100, value: 60 }];
+        let groups = vec![BrightnessGroup {
+            name: "Mesa".to_string(),
+            members: vec![
+                GroupMember { monitor: "A".to_string(), curve: Vec::new() },
+                GroupMember { monitor: "B".to_string(), curve: curve.clone() },
+                GroupMember { monitor: "C".to_string(), curve: Vec::new() },
+            ],
+        }];
+        let schedule = |monitor: &str| MonitorSchedule { monitor: monitor.to_string(), anchors: Vec::new() };
+
+        assert_eq!(followers("X", &groups, &[schedule("X")]), [("X", &[][..])]);
+        assert_eq!(
+            followers("A", &groups, &[schedule("A")]),
+            [("A", &[][..]), ("B", curve.as_slice()), ("C", &[][..])]
+        );
+        // C tem agendamento próprio e não segue o de A; B fica com o primeiro da lista.
+        assert_eq!(followers("A", &groups, &[schedule("A"), schedule("C")]), [("A", &[][..]), ("B", curve.as_slice())]);
+        assert_eq!(followers("C", &groups, &[schedule("A"), schedule("C")]), [("C", &[][..])]);
+        assert_eq!(followers("B", &groups, &[schedule("C"), schedule("B")]), [("B", curve.as_slice())]);
+    }
+}
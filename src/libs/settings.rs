@@ -0,0 +1,255 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use super::ddc::DdcConfig;
+use super::functions::DisplaySummary;
+use super::groups::BrightnessGroup;
+use super::hotkeys::HotkeySettings;
+use super::i18n::{Language, t};
+use super::ipc::IpcSettings;
+use super::layouts::Layout;
+use super::profiles::Profile;
+use super::schedule::ScheduleSettings;
+use super::tray::TraySettings;
+
+// Sobe a cada mudança incompatível no formato, com uma entrada nova em MIGRATIONS.
+pub const SETTINGS_VERSION: u32 = 1;
+
+// MIGRATIONS[n] leva um arquivo da versão n para a n + 1.
+const MIGRATIONS: [fn(&mut toml::Table); SETTINGS_VERSION as usize] = [
+    // Arquivos anteriores ao campo "version" já têm o formato da versão 1.
+    |_| {},
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub version: u32,
+    pub interface: InterfaceSettings,
+    pub schedule: ScheduleSettings,
+    pub groups: Vec<BrightnessGroup>,
+    pub ddc: DdcConfig,
+    pub nicknames: BTreeMap<String, String>,
+    pub ipc: IpcSettings,
+    pub hotkeys: HotkeySettings,
+    pub profiles: Vec<Profile>,
+    pub layouts: Vec<Layout>,
+    pub tray: TraySettings,
+    pub language: Language,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            interface: InterfaceSettings::default(),
+            schedule: ScheduleSettings::default(),
+            groups: Vec::new(),
+            ddc: DdcConfig::default(),
+            nicknames: BTreeMap::new(),
+            ipc: IpcSettings::default(),
+            hotkeys: HotkeySettings::default(),
+            profiles: Vec::new(),
+            layouts: Vec::new(),
+            tray: TraySettings::default(),
+            language: Language::default(),
+        }
+    }
+}
+
+impl Settings {
+    // Versões antigas guardavam os monitores pelo nome GDI (\\.\DISPLAY1), que muda
+    // com a ordem das portas. A troca pela chave estável depende dos monitores
+    // conectados, então acontece em tempo de execução e não em MIGRATIONS.
+    pub fn migrate_monitor_keys(&mut self, displays: &[DisplaySummary]) -> bool {
+        let mut changed = false;
+        let mut migrate = |monitor: &mut String| {
+            if let Some(display) = displays.iter().find(|d| d.name == *monitor && d.key != d.name) {
+                *monitor = display.key.clone();
+                changed = true;
+            }
+        };
+
+        let nicknames = std::mem::take(&mut self.nicknames);
+        for (mut monitor, nickname) in nicknames {
+            migrate(&mut monitor);
+            self.nicknames.entry(monitor).or_insert(nickname);
+        }
+        for member in self.groups.iter_mut().flat_map(|g| &mut g.members) {
+            migrate(&mut member.monitor);
+        }
+        for schedule in &mut self.schedule.monitors {
+            migrate(&mut schedule.monitor);
+        }
+        for entry in self.profiles.iter_mut().flat_map(|p| &mut p.monitors) {
+            migrate(&mut entry.monitor);
+        }
+        for entry in self.layouts.iter_mut().flat_map(|l| &mut l.monitors) {
+            migrate(&mut entry.monitor);
+        }
+        if let Some(monitor) = &mut self.interface.last_monitor {
+            migrate(monitor);
+        }
+
+        changed
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefreshPolicy {
+    // Só lê o monitor ao selecioná-lo.
+    Manual,
+    // Lê de novo depois de cada alteração.
+    #[default]
+    AfterChanges,
+    // Como AfterChanges, e também a cada refresh_interval segundos.
+    Periodic,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InterfaceSettings {
+    pub font_size: f32,
+    pub window_size: [f32; 2],
+    pub last_monitor: Option<String>,
+    pub refresh: RefreshPolicy,
+    pub refresh_interval: u32,
+    // Segundos para confirmar uma nova resolução antes de reverter; 0 desativa.
+    pub confirm_timeout: u32,
+}
+
+impl Default for InterfaceSettings {
+    fn default() -> Self {
+        Self {
+            font_size: 15.0,
+            window_size: [367.0, 375.0],
+            last_monitor: None,
+            refresh: RefreshPolicy::AfterChanges,
+            refresh_interval: 30,
+            confirm_timeout: 15,
+        }
+    }
+}
+
+pub fn settings_path() -> PathBuf {
+    let base = std::env::var_os("APPDATA").map(PathBuf::from).unwrap_or_default();
+    base.join("DisplayManager").join("settings.toml")
+}
+
+// Devolve também a versão encontrada no arquivo, antes da migração.
+fn parse_settings(text: &str) -> Result<(Settings, u32), String> {
+    let mut table: toml::Table = text.parse().map_err(|e: toml::de::Error| e.message().to_string())?;
+    let version = match table.get("version") {
+        None => 0,
+        Some(toml::Value::Integer(v)) if *v >= 0 => *v as u32,
+        Some(_) => return Err(t!("settings-invalid-version")),
+    };
+    for migrate in MIGRATIONS.iter().skip(version as usize) {
+        migrate(&mut table);
+    }
+    table.insert("version".to_string(), toml::Value::Integer(SETTINGS_VERSION as i64));
+    let settings = toml::Value::Table(table).try_into().map_err(|e: toml::de::Error| e.message().to_string())?;
+    Ok((settings, version))
+}
+
+// Um arquivo inválido vira padrões e um aviso, nunca um erro fatal.
+pub fn read_settings() -> (Settings, Option<String>) {
+    let path = settings_path();
+    let Ok(text) = fs::read_to_string(&path) else {
+        return (Settings::default(), None);
+    };
+    match parse_settings(&text) {
+        // Feito por uma versão mais nova do programa: campos desconhecidos se perdem ao salvar.
+        Ok((settings, version)) if version > SETTINGS_VERSION => {
+            (settings, Some(t!("settings-newer-version", version = version, supported = SETTINGS_VERSION)))
+        }
+        Ok((settings, _)) => (settings, None),
+        Err(reason) => (Settings::default(), Some(t!("settings-invalid-file", path = path.display().to_string(), reason = reason))),
+    }
+}
+
+// Permite a outro processo perceber que a janela salvou alterações.
+pub fn settings_modified() -> Option<SystemTime> {
+    fs::metadata(settings_path()).and_then(|m| m.modified()).ok()
+}
+
+pub fn load_settings() -> Settings {
+    read_settings().0
+}
+
+pub fn save_settings(settings: &Settings) -> io::Result<()> {
+    let path = settings_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // Guarda uma cópia do arquivo inválido em vez de sobrescrevê-lo sem aviso.
+    if let Ok(text) = fs::read_to_string(&path)
+        && parse_settings(&text).is_err()
+    {
+        fs::copy(&path, path.with_extension("toml.invalid"))?;
+    }
+    let text = toml::to_string_pretty(settings).map_err(io::Error::other)?;
+    fs::write(path, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::groups::GroupMember;
+    use crate::libs::layouts::LayoutEntry;
+    use crate::libs::profiles::ProfileEntry;
+
+    fn display(index: u32, key: &str) -> DisplaySummary {
+        DisplaySummary {
+            index,
+            name: format!("\\\\.\\DISPLAY{}", index + 1),
+            key: key.to_string(),
+            friendly_name: String::new(),
+            connector: None,
+        }
+    }
+
+    #[test]
+    fn migrates_gdi_names_to_stable_keys() {
+        let left = "\\\\.\\DISPLAY1".to_string();
+        let gone = "\\\\.\\DISPLAY3".to_string();
+        let mut settings = Settings::default();
+        settings.nicknames.insert(left.clone(), "Esquerda".to_string());
+        settings.nicknames.insert(gone.clone(), "Antigo".to_string());
+        settings.groups.push(BrightnessGroup {
+            name: "Mesa".to_string(),
+            members: vec![GroupMember { monitor: left.clone(), curve: Vec::new() }],
+        });
+        settings.schedule.anchors_mut(&left);
+        settings.profiles.push(Profile {
+            name: "Noite".to_string(),
+            monitors: vec![ProfileEntry { monitor: "\\\\.\\DISPLAY2".to_string(), brightness: Some(20), input_source: None }],
+        });
+        settings.layouts.push(Layout {
+            name: "Mesa".to_string(),
+            monitors: vec![LayoutEntry { monitor: left.clone(), x: 0, y: 0, width: 2560, height: 1440, primary: true }],
+        });
+        settings.interface.last_monitor = Some(left.clone());
+
+        // O segundo monitor não tem EDID e continua com o nome GDI como chave.
+        let displays = [display(0, "DEL-A0A5-ABC123"), display(1, "\\\\.\\DISPLAY2")];
+        assert!(settings.migrate_monitor_keys(&displays));
+
+        assert_eq!(settings.nicknames.get("DEL-A0A5-ABC123").map(String::as_str), Some("Esquerda"));
+        assert_eq!(settings.nicknames.get(&gone).map(String::as_str), Some("Antigo"));
+        assert!(!settings.nicknames.contains_key(&left));
+        assert_eq!(settings.groups[0].members[0].monitor, "DEL-A0A5-ABC123");
+        assert_eq!(settings.schedule.monitors[0].monitor, "DEL-A0A5-ABC123");
+        assert_eq!(settings.profiles[0].monitors[0].monitor, "\\\\.\\DISPLAY2");
+        assert_eq!(settings.layouts[0].monitors[0].monitor, "DEL-A0A5-ABC123");
+        assert_eq!(settings.interface.last_monitor.as_deref(), Some("DEL-A0A5-ABC123"));
+
+        assert!(!settings.migrate_monitor_keys(&displays));
+    }
+}
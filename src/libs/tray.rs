@@ -0,0 +1,371 @@
+use std::process;
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+use windows::Win32::Foundation::{
+    CloseHandle,
+    ERROR_ALREADY_EXISTS,
+    GetLastError,
+    HINSTANCE,
+    HWND,
+    LPARAM,
+    LRESULT,
+    POINT,
+    WPARAM,
+};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::System::Registry::{
+    HKEY_CURRENT_USER,
+    REG_SZ,
+    RRF_RT_REG_SZ,
+    RegDeleteKeyValueW,
+    RegGetValueW,
+    RegSetKeyValueW,
+};
+use windows::Win32::System::Threading::{CreateMutexW, OpenMutexW, SYNCHRONIZATION_SYNCHRONIZE};
+use windows::Win32::UI::Shell::{
+    NIF_ICON,
+    NIF_MESSAGE,
+    NIF_TIP,
+    NIM_ADD,
+    NIM_DELETE,
+    NOTIFYICONDATAW,
+    Shell_NotifyIconW,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    AppendMenuW,
+    CreatePopupMenu,
+    CreateWindowExW,
+    DefWindowProcW,
+    DestroyMenu,
+    DispatchMessageW,
+    GetCursorPos,
+    GetMessageW,
+    HICON,
+    HMENU,
+    IDI_APPLICATION,
+    LoadIconW,
+    MF_POPUP,
+    MF_SEPARATOR,
+    MF_STRING,
+    MSG,
+    PostMessageW,
+    PostQuitMessage,
+    RegisterClassW,
+    RegisterWindowMessageW,
+    SetForegroundWindow,
+    TPM_RETURNCMD,
+    TPM_RIGHTBUTTON,
+    TrackPopupMenu,
+    TranslateMessage,
+    WINDOW_EX_STYLE,
+    WINDOW_STYLE,
+    WM_APP,
+    WM_CONTEXTMENU,
+    WM_LBUTTONDBLCLK,
+    WM_NULL,
+    WM_RBUTTONUP,
+    WNDCLASSW,
+};
+use windows::core::{PCWSTR, Result};
+
+use super::functions::{PhysicalTarget, display_brightness, list_displays};
+use super::i18n::t;
+use super::layouts::{Layout, apply_layout};
+use super::profiles::{Profile, apply_profile};
+use super::settings::load_settings;
+
+const WM_TRAY: u32 = WM_APP + 2;
+const RUN_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Run";
+const RUN_VALUE: &str = "DisplayManager";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TraySettings {
+    pub presets: Vec<u32>,
+}
+
+impl Default for TraySettings {
+    fn default() -> Self {
+        Self { presets: vec![0, 25, 50, 75, 100] }
+    }
+}
+
+enum MenuAction {
+    Brightness { index: Option<u32>, percent: u32 },
+    Profile(Profile),
+    Layout(Layout),
+    Open,
+    Quit,
+}
+
+fn wide(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+pub fn start_at_login() -> bool {
+    let key = wide(RUN_KEY);
+    let value = wide(RUN_VALUE);
+    unsafe {
+        RegGetValueW(HKEY_CURRENT_USER, PCWSTR(key.as_ptr()), PCWSTR(value.as_ptr()), RRF_RT_REG_SZ, None, None, None)
+    }
+    .is_ok()
+}
+
+pub fn set_start_at_login(enabled: bool) -> Result<()> {
+    let key = wide(RUN_KEY);
+    let value = wide(RUN_VALUE);
+
+    if !enabled {
+        return unsafe { RegDeleteKeyValueW(HKEY_CURRENT_USER, PCWSTR(key.as_ptr()), PCWSTR(value.as_ptr())) }.ok();
+    }
+
+    let command = wide(&format!("\"{}\" --background", std::env::current_exe()?.display()));
+    unsafe {
+        RegSetKeyValueW(
+            HKEY_CURRENT_USER,
+            PCWSTR(key.as_ptr()),
+            PCWSTR(value.as_ptr()),
+            REG_SZ.0,
+            Some(command.as_ptr() as *const _),
+            (command.len() * 2) as u32,
+        )
+    }
+    .ok()
+}
+
+// Abre o modo de bandeja em outro processo; se já houver um, ele mesmo desiste.
+pub fn spawn_background() -> Result<()> {
+    process::Command::new(std::env::current_exe()?).arg("--background").spawn()?;
+    Ok(())
+}
+
+// O mutex fica aberto até o processo sair e marca a instância em segundo plano.
+const BACKGROUND_MUTEX: &str = "Local\\DisplayManager.Background";
+
+pub fn background_running() -> bool {
+    let name = wide(BACKGROUND_MUTEX);
+    let created = unsafe { CreateMutexW(None, false, PCWSTR(name.as_ptr())) };
+    created.is_ok() && unsafe { GetLastError() } == ERROR_ALREADY_EXISTS
+}
+
+// Só consulta: ao contrário de background_running, não cria o mutex.
+pub fn background_active() -> bool {
+    let name = wide(BACKGROUND_MUTEX);
+    match unsafe { OpenMutexW(SYNCHRONIZATION_SYNCHRONIZE, false, PCWSTR(name.as_ptr())) } {
+        Ok(handle) => {
+            let _ = unsafe { CloseHandle(handle) };
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+// Bloqueia até o usuário escolher "Sair" no menu.
+pub fn run_tray() -> Result<()> {
+    let instance: HINSTANCE = unsafe { GetModuleHandleW(None)? }.into();
+    let class = wide("DisplayManagerTray");
+    let window_class = WNDCLASSW {
+        lpfnWndProc: Some(window_proc),
+        hInstance: instance,
+        lpszClassName: PCWSTR(class.as_ptr()),
+        ..Default::default()
+    };
+    unsafe { RegisterClassW(&window_class) };
+
+    let title = wide("Display Manager");
+    let hwnd = unsafe {
+        CreateWindowExW(
+            WINDOW_EX_STYLE(0),
+            PCWSTR(class.as_ptr()),
+            PCWSTR(title.as_ptr()),
+            WINDOW_STYLE(0),
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            Some(instance),
+            None,
+        )?
+    };
+    add_icon(hwnd);
+
+    let mut msg = MSG::default();
+    while unsafe { GetMessageW(&mut msg, None, 0, 0) }.as_bool() {
+        unsafe {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    }
+
+    Ok(())
+}
+
+fn notify_icon_data(hwnd: HWND) -> NOTIFYICONDATAW {
+    NOTIFYICONDATAW {
+        cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
+        hWnd: hwnd,
+        uID: 1,
+        ..Default::default()
+    }
+}
+
+fn add_icon(hwnd: HWND) {
+    let mut data = notify_icon_data(hwnd);
+    data.uFlags = NIF_ICON | NIF_MESSAGE | NIF_TIP;
+    data.uCallbackMessage = WM_TRAY;
+    data.hIcon = load_icon();
+    for (dst, src) in data.szTip.iter_mut().zip("Display Manager".encode_utf16()) {
+        *dst = src;
+    }
+    unsafe {
+        let _ = Shell_NotifyIconW(NIM_ADD, &data);
+    }
+}
+
+fn load_icon() -> HICON {
+    // O winres grava o ícone do executável com o id 1.
+    unsafe {
+        GetModuleHandleW(None)
+            .and_then(|module| LoadIconW(Some(module.into()), PCWSTR(1 as _)))
+            .or_else(|_| LoadIconW(None, IDI_APPLICATION))
+            .unwrap_or_default()
+    }
+}
+
+unsafe extern "system" fn window_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    // O Explorer recriado (após travar ou reiniciar) perde os ícones da bandeja.
+    let taskbar_created = unsafe { RegisterWindowMessageW(PCWSTR(wide("TaskbarCreated").as_ptr())) };
+
+    match msg {
+        WM_TRAY => {
+            match lparam.0 as u32 {
+                WM_RBUTTONUP | WM_CONTEXTMENU => show_menu(hwnd),
+                WM_LBUTTONDBLCLK => open_window(),
+                _ => {}
+            }
+            LRESULT(0)
+        }
+        _ if msg == taskbar_created => {
+            add_icon(hwnd);
+            LRESULT(0)
+        }
+        _ => unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) },
+    }
+}
+
+fn show_menu(hwnd: HWND) {
+    let Ok(menu) = (unsafe { CreatePopupMenu() }) else {
+        return;
+    };
+    let actions = build_menu(menu);
+
+    let mut point = POINT::default();
+    let choice = unsafe {
+        let _ = GetCursorPos(&mut point);
+        // Sem isto o menu não fecha ao clicar fora dele.
+        let _ = SetForegroundWindow(hwnd);
+        let choice = TrackPopupMenu(menu, TPM_RETURNCMD | TPM_RIGHTBUTTON, point.x, point.y, None, hwnd, None);
+        let _ = PostMessageW(Some(hwnd), WM_NULL, WPARAM(0), LPARAM(0));
+        let _ = DestroyMenu(menu);
+        choice.0 as usize
+    };
+
+    // Os ids começam em 1; 0 significa menu fechado sem escolha.
+    let Some(action) = choice.checked_sub(1).and_then(|i| actions.into_iter().nth(i)) else {
+        return;
+    };
+    match action {
+        MenuAction::Brightness { index, percent } => {
+            thread::spawn(move || {
+                let indices = match index {
+                    Some(index) => vec![index],
+                    None => list_displays().unwrap_or_default().into_iter().map(|d| d.index).collect(),
+                };
+                for index in indices {
+                    let _ = display_brightness(index, PhysicalTarget::All, percent);
+                }
+            });
+        }
+        MenuAction::Profile(profile) => {
+            thread::spawn(move || apply_profile(&profile));
+        }
+        MenuAction::Layout(layout) => {
+            thread::spawn(move || apply_layout(&layout));
+        }
+        MenuAction::Open => open_window(),
+        MenuAction::Quit => unsafe {
+            let _ = Shell_NotifyIconW(NIM_DELETE, &notify_icon_data(hwnd));
+            PostQuitMessage(0);
+        },
+    }
+}
+
+fn build_menu(menu: HMENU) -> Vec<MenuAction> {
+    // Lidas a cada abertura para refletir o que a janela salvou.
+    let settings = load_settings();
+    let mut actions = Vec::new();
+    let mut item = |menu: HMENU, label: &str, action: MenuAction| {
+        actions.push(action);
+        let label = wide(label);
+        unsafe {
+            let _ = AppendMenuW(menu, MF_STRING, actions.len(), PCWSTR(label.as_ptr()));
+        }
+    };
+    let submenu = |menu: HMENU, label: &str| -> Option<HMENU> {
+        let submenu = unsafe { CreatePopupMenu() }.ok()?;
+        let label = wide(label);
+        unsafe {
+            let _ = AppendMenuW(menu, MF_POPUP, submenu.0 as usize, PCWSTR(label.as_ptr()));
+        }
+        Some(submenu)
+    };
+
+    let mut targets: Vec<(Option<u32>, String)> = list_displays()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|d| (Some(d.index), d.label(settings.nicknames.get(&d.key).map(String::as_str))))
+        .collect();
+    if targets.len() > 1 {
+        targets.push((None, t!("tray-all-monitors")));
+    }
+    for (index, label) in targets {
+        if let Some(brightness) = submenu(menu, &label) {
+            for &percent in &settings.tray.presets {
+                item(brightness, &t!("tray-brightness", percent = percent), MenuAction::Brightness { index, percent });
+            }
+        }
+    }
+
+    if !settings.profiles.is_empty()
+        && let Some(profiles) = submenu(menu, &t!("tray-profiles"))
+    {
+        for profile in settings.profiles {
+            item(profiles, &profile.name.clone(), MenuAction::Profile(profile));
+        }
+    }
+
+    if !settings.layouts.is_empty()
+        && let Some(layouts) = submenu(menu, &t!("tray-layouts"))
+    {
+        for layout in settings.layouts {
+            item(layouts, &layout.name.clone(), MenuAction::Layout(layout));
+        }
+    }
+
+    unsafe {
+        let _ = AppendMenuW(menu, MF_SEPARATOR, 0, PCWSTR::null());
+    }
+    item(menu, &t!("tray-open"), MenuAction::Open);
+    item(menu, &t!("tray-quit"), MenuAction::Quit);
+
+    actions
+}
+
+fn open_window() {
+    if let Ok(exe) = std::env::current_exe() {
+        let _ = process::Command::new(exe).spawn();
+    }
+}
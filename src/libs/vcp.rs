@@ -0,0 +1,280 @@
+pub const RESTORE_FACTORY_DEFAULTS: u8 = 0x04;
+pub const RESTORE_LUMINANCE_CONTRAST: u8 = 0x05;
+pub const RESTORE_GEOMETRY: u8 = 0x06;
+pub const RESTORE_COLOR: u8 = 0x08;
+pub const BRIGHTNESS: u8 = 0x10;
+pub const CONTRAST: u8 = 0x12;
+pub const COLOR_PRESET: u8 = 0x14;
+pub const RED_GAIN: u8 = 0x16;
+pub const GREEN_GAIN: u8 = 0x18;
+pub const BLUE_GAIN: u8 = 0x1A;
+pub const AUTO_SETUP: u8 = 0x1E;
+pub const HORIZONTAL_POSITION: u8 = 0x20;
+pub const HORIZONTAL_SIZE: u8 = 0x22;
+pub const VERTICAL_POSITION: u8 = 0x30;
+pub const VERTICAL_SIZE: u8 = 0x32;
+pub const INPUT_SOURCE: u8 = 0x60;
+pub const RED_BLACK_LEVEL: u8 = 0x6C;
+pub const GREEN_BLACK_LEVEL: u8 = 0x6E;
+pub const BLUE_BLACK_LEVEL: u8 = 0x70;
+pub const GAMMA: u8 = 0x72;
+pub const SHARPNESS: u8 = 0x87;
+pub const POWER_MODE: u8 = 0xD6;
+
+use super::i18n::{t, tr};
+
+// Os textos são ids do catálogo de mensagens; nomes universais (VGA, HDMI, 6500 K…)
+// não têm mensagem e são exibidos como estão.
+pub const IMAGE_CONTROLS: [(u8, &str); 10] = [
+    (CONTRAST, "vcp-contrast"),
+    (COLOR_PRESET, "vcp-color-preset"),
+    (RED_GAIN, "vcp-red-gain"),
+    (GREEN_GAIN, "vcp-green-gain"),
+    (BLUE_GAIN, "vcp-blue-gain"),
+    (RED_BLACK_LEVEL, "vcp-red-black-level"),
+    (GREEN_BLACK_LEVEL, "vcp-green-black-level"),
+    (BLUE_BLACK_LEVEL, "vcp-blue-black-level"),
+    (SHARPNESS, "vcp-sharpness"),
+    (GAMMA, "vcp-gamma"),
+];
+
+const INPUT_SOURCE_NAMES: [(u8, &str); 19] = [
+    (0x01, "VGA 1"),
+    (0x02, "VGA 2"),
+    (0x03, "DVI 1"),
+    (0x04, "DVI 2"),
+    (0x05, "input-composite-1"),
+    (0x06, "input-composite-2"),
+    (0x07, "S-Video 1"),
+    (0x08, "S-Video 2"),
+    (0x09, "input-tuner-1"),
+    (0x0A, "input-tuner-2"),
+    (0x0B, "input-tuner-3"),
+    (0x0C, "input-component-1"),
+    (0x0D, "input-component-2"),
+    (0x0E, "input-component-3"),
+    (0x0F, "DisplayPort 1"),
+    (0x10, "DisplayPort 2"),
+    (0x11, "HDMI 1"),
+    (0x12, "HDMI 2"),
+    (0x1B, "USB-C"),
+];
+
+const COLOR_PRESET_NAMES: [(u8, &str); 13] = [
+    (0x01, "sRGB"),
+    (0x02, "color-preset-native"),
+    (0x03, "4000 K"),
+    (0x04, "5000 K"),
+    (0x05, "6500 K"),
+    (0x06, "7500 K"),
+    (0x07, "8200 K"),
+    (0x08, "9300 K"),
+    (0x09, "10000 K"),
+    (0x0A, "11500 K"),
+    (0x0B, "color-preset-user-1"),
+    (0x0C, "color-preset-user-2"),
+    (0x0D, "color-preset-user-3"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerMode {
+    On = 0x01,
+    Standby = 0x02,
+    Suspend = 0x03,
+    Off = 0x04,
+    HardOff = 0x05,
+}
+
+impl PowerMode {
+    pub fn from_value(value: u32) -> Option<PowerMode> {
+        match value & 0xFF {
+            0x01 => Some(PowerMode::On),
+            0x02 => Some(PowerMode::Standby),
+            0x03 => Some(PowerMode::Suspend),
+            0x04 => Some(PowerMode::Off),
+            0x05 => Some(PowerMode::HardOff),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetCategory {
+    Factory,
+    LuminanceContrast,
+    Geometry,
+    Color,
+}
+
+const LUMINANCE_CODES: [u8; 2] = [BRIGHTNESS, CONTRAST];
+const GEOMETRY_CODES: [u8; 4] = [HORIZONTAL_POSITION, HORIZONTAL_SIZE, VERTICAL_POSITION, VERTICAL_SIZE];
+const COLOR_CODES: [u8; 8] = [
+    COLOR_PRESET,
+    RED_GAIN,
+    GREEN_GAIN,
+    BLUE_GAIN,
+    RED_BLACK_LEVEL,
+    GREEN_BLACK_LEVEL,
+    BLUE_BLACK_LEVEL,
+    GAMMA,
+];
+
+impl ResetCategory {
+    pub const ALL: [ResetCategory; 4] = [
+        ResetCategory::Factory,
+        ResetCategory::LuminanceContrast,
+        ResetCategory::Geometry,
+        ResetCategory::Color,
+    ];
+
+    pub fn code(self) -> u8 {
+        match self {
+            ResetCategory::Factory => RESTORE_FACTORY_DEFAULTS,
+            ResetCategory::LuminanceContrast => RESTORE_LUMINANCE_CONTRAST,
+            ResetCategory::Geometry => RESTORE_GEOMETRY,
+            ResetCategory::Color => RESTORE_COLOR,
+        }
+    }
+
+    // Códigos que a redefinição pode alterar; são lidos antes para permitir desfazer.
+    pub fn affected_codes(self) -> Vec<u8> {
+        match self {
+            ResetCategory::Factory => [&LUMINANCE_CODES[..], &GEOMETRY_CODES, &COLOR_CODES, &[SHARPNESS]].concat(),
+            ResetCategory::LuminanceContrast => LUMINANCE_CODES.to_vec(),
+            ResetCategory::Geometry => GEOMETRY_CODES.to_vec(),
+            ResetCategory::Color => COLOR_CODES.to_vec(),
+        }
+    }
+
+    // Nome usado na linha de comando (--only).
+    pub fn name(self) -> &'static str {
+        match self {
+            ResetCategory::Factory => "factory",
+            ResetCategory::LuminanceContrast => "luminance",
+            ResetCategory::Geometry => "geometry",
+            ResetCategory::Color => "color",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn label(self) -> String {
+        tr(match self {
+            ResetCategory::Factory => "reset-factory",
+            ResetCategory::LuminanceContrast => "reset-luminance-contrast",
+            ResetCategory::Geometry => "reset-geometry",
+            ResetCategory::Color => "reset-color",
+        })
+    }
+
+    pub fn description(self) -> String {
+        tr(match self {
+            ResetCategory::Factory => "reset-factory-description",
+            ResetCategory::LuminanceContrast => "reset-luminance-contrast-description",
+            ResetCategory::Geometry => "reset-geometry-description",
+            ResetCategory::Color => "reset-color-description",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VcpKind {
+    SetParameter,
+    Momentary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VcpValue {
+    pub current: u32,
+    pub max: u32,
+    pub kind: VcpKind,
+}
+
+impl VcpValue {
+    pub fn percent(&self) -> u32 {
+        to_percent(self.current, self.max)
+    }
+}
+
+pub fn to_percent(value: u32, max: u32) -> u32 {
+    if max == 0 {
+        return 0;
+    }
+    let value = value.min(max) as u64;
+    ((value * 100 + max as u64 / 2) / max as u64) as u32
+}
+
+pub fn from_percent(percent: u32, max: u32) -> u32 {
+    let percent = percent.min(100) as u64;
+    ((percent * max as u64 + 50) / 100) as u32
+}
+
+pub fn input_source_name(value: u8) -> String {
+    INPUT_SOURCE_NAMES
+        .iter()
+        .find(|(v, _)| *v == value)
+        .map(|(_, name)| tr(name))
+        .unwrap_or_else(|| t!("input-unknown", code = format!("0x{:02X}", value)))
+}
+
+pub fn standard_input_sources() -> Vec<u8> {
+    INPUT_SOURCE_NAMES.iter().map(|(v, _)| *v).collect()
+}
+
+pub fn value_name(code: u8, value: u8) -> String {
+    let named = |names: &[(u8, &str)]| {
+        names.iter().find(|(v, _)| *v == value).map(|(_, name)| tr(name))
+    };
+
+    match code {
+        INPUT_SOURCE => input_source_name(value),
+        COLOR_PRESET => named(&COLOR_PRESET_NAMES).unwrap_or_else(|| t!("color-preset-unknown", code = format!("0x{:02X}", value))),
+        // MCCS codifica o gama como (gama - 1) * 100.
+        GAMMA => format!("{:.2}", (value as f32 + 100.0) / 100.0),
+        _ => format!("0x{:02X}", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percent_with_zero_max() {
+        assert_eq!(to_percent(0, 0), 0);
+        assert_eq!(to_percent(50, 0), 0);
+        assert_eq!(from_percent(50, 0), 0);
+    }
+
+    #[test]
+    fn percent_rounds_to_nearest() {
+        assert_eq!(to_percent(1, 3), 33);
+        assert_eq!(to_percent(2, 3), 67);
+        assert_eq!(to_percent(50, 255), 20);
+        assert_eq!(from_percent(50, 255), 128);
+        assert_eq!(from_percent(33, 3), 1);
+        assert_eq!(from_percent(67, 3), 2);
+    }
+
+    #[test]
+    fn percent_clamps_out_of_range() {
+        assert_eq!(to_percent(150, 100), 100);
+        assert_eq!(from_percent(150, 100), 100);
+        assert_eq!(from_percent(u32::MAX, 255), 255);
+    }
+
+    #[test]
+    fn percent_round_trip() {
+        for max in [1, 3, 50, 100, 255, 1000] {
+            for value in 0..=max {
+                let percent = to_percent(value, max);
+                assert!(percent <= 100);
+                // Ida e volta erra no máximo meio ponto percentual mais o arredondamento.
+                let back = from_percent(percent, max);
+                assert!(back.abs_diff(value) * 200 <= max + 100, "max {max}, valor {value}, volta {back}");
+            }
+        }
+    }
+}
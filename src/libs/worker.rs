@@ -0,0 +1,459 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use windows::core::{HRESULT, Result};
+
+use super::capabilities::Capabilities;
+use super::edid::Edid;
+use super::i18n::{t, tr};
+use super::inventory::export_inventory;
+use super::layouts::{Layout, apply_layout, capture_layout};
+use super::profiles::{Profile, apply_profile, capture_profile};
+use super::functions::{
+    DisplayBackend,
+    DisplaySummary,
+    PhysicalMonitor,
+    PhysicalTarget,
+    Position,
+    SystemDisplays,
+    Topology,
+    auto_adjust,
+    connected_displays,
+    display_topology,
+    find_properties,
+    get_capabilities,
+    get_edid,
+    get_input_source,
+    get_power_mode,
+    get_vcp,
+    list_displays,
+    list_physical_monitors,
+    read_vcp_values,
+    reset_monitor,
+    set_input_source,
+    set_power_mode,
+    set_primary_display,
+    set_resolution,
+    set_vcp,
+    turn_off_others,
+};
+use super::vcp::{BRIGHTNESS, IMAGE_CONTROLS, INPUT_SOURCE, POWER_MODE, PowerMode, ResetCategory, VcpValue};
+
+const TOPOLOGY_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    Refresh { index: u32, physical: PhysicalTarget, capabilities: Option<Capabilities> },
+    Overview,
+    SetResolution { index: u32, width: u32, height: u32 },
+    SetPrimary { index: u32 },
+    MoveLeft { index: u32 },
+    MoveRight { index: u32 },
+    Move { index: u32, x: i32, y: i32 },
+    AutoAdjust { index: u32, physical: PhysicalTarget },
+    Reset { index: u32, physical: PhysicalTarget, categories: Vec<ResetCategory> },
+    RestoreVcp { backup: VcpBackup },
+    SetVcp { index: u32, physical: PhysicalTarget, code: u8, value: u32 },
+    SetInputSource { index: u32, physical: PhysicalTarget, source: u8 },
+    SetPowerMode { index: u32, physical: PhysicalTarget, mode: PowerMode },
+    TurnOffOthers { index: u32, mode: PowerMode },
+    ExportInventory { path: PathBuf, nicknames: BTreeMap<String, String> },
+    CaptureProfile { name: String },
+    ApplyProfile { profile: Profile },
+    CaptureLayout { name: String },
+    ApplyLayout { layout: Layout },
+}
+
+impl Command {
+    pub fn label(&self) -> String {
+        tr(match self {
+            Command::Refresh { .. } => "task-refresh",
+            Command::Overview => "task-overview",
+            Command::SetResolution { .. } => "task-set-resolution",
+            Command::SetPrimary { .. } => "task-set-primary",
+            Command::MoveLeft { .. } | Command::MoveRight { .. } | Command::Move { .. } => "task-move",
+            Command::AutoAdjust { .. } => "task-auto-adjust",
+            Command::Reset { .. } => "task-reset",
+            Command::RestoreVcp { .. } => "task-restore-vcp",
+            Command::SetVcp { .. } => "task-set-vcp",
+            Command::SetInputSource { .. } => "task-set-input",
+            Command::SetPowerMode { .. } => "task-set-power",
+            Command::TurnOffOthers { .. } => "task-turn-off-others",
+            Command::ExportInventory { .. } => "task-export-inventory",
+            Command::CaptureProfile { .. } => "task-capture-profile",
+            Command::ApplyProfile { .. } => "task-apply-profile",
+            Command::CaptureLayout { .. } => "task-capture-layout",
+            Command::ApplyLayout { .. } => "task-apply-layout",
+        })
+    }
+
+    // Monitor afetado, para identificar a origem de uma falha.
+    pub fn index(&self) -> Option<u32> {
+        match self {
+            Command::Refresh { index, .. }
+            | Command::SetResolution { index, .. }
+            | Command::SetPrimary { index }
+            | Command::MoveLeft { index }
+            | Command::MoveRight { index }
+            | Command::Move { index, .. }
+            | Command::AutoAdjust { index, .. }
+            | Command::Reset { index, .. }
+            | Command::SetVcp { index, .. }
+            | Command::SetInputSource { index, .. }
+            | Command::SetPowerMode { index, .. }
+            | Command::TurnOffOthers { index, .. } => Some(*index),
+            Command::RestoreVcp { backup } => Some(backup.index),
+            Command::Overview
+            | Command::ExportInventory { .. }
+            | Command::CaptureProfile { .. }
+            | Command::ApplyProfile { .. }
+            | Command::CaptureLayout { .. }
+            | Command::ApplyLayout { .. } => None,
+        }
+    }
+
+    // Confirmação para operações demoradas; as instantâneas se confirmam sozinhas na tela.
+    pub fn done_label(&self) -> Option<String> {
+        let id = match self {
+            Command::SetResolution { .. } => "done-set-resolution",
+            Command::SetPrimary { .. } => "done-set-primary",
+            Command::MoveLeft { .. } | Command::MoveRight { .. } | Command::Move { .. } => "done-move",
+            Command::AutoAdjust { .. } => "done-auto-adjust",
+            Command::Reset { .. } => "done-reset",
+            Command::RestoreVcp { .. } => "done-restore-vcp",
+            Command::SetInputSource { .. } => "done-set-input",
+            Command::SetPowerMode { .. } => "done-set-power",
+            Command::TurnOffOthers { .. } => "done-turn-off-others",
+            Command::CaptureProfile { .. } => "done-capture-profile",
+            Command::ApplyProfile { .. } => "done-apply-profile",
+            Command::CaptureLayout { .. } => "done-capture-layout",
+            Command::ApplyLayout { .. } => "done-apply-layout",
+            _ => return None,
+        };
+        Some(tr(id))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MonitorState {
+    pub index: u32,
+    pub physical: PhysicalTarget,
+    pub displays: Vec<DisplaySummary>,
+    pub name: String,
+    pub is_primary: bool,
+    pub physical_monitors: Vec<PhysicalMonitor>,
+    pub capabilities: Option<Capabilities>,
+    pub edid: Option<Edid>,
+    pub brightness: Option<VcpValue>,
+    pub input_source: Option<u8>,
+    pub image_controls: Vec<(u8, VcpValue)>,
+    pub power_mode: Option<PowerMode>,
+}
+
+impl MonitorState {
+    pub fn advertises(&self, code: u8) -> bool {
+        match &self.capabilities {
+            Some(caps) => caps.supports(code),
+            None => self.brightness.is_some(),
+        }
+    }
+}
+
+// O essencial de cada monitor, sem DDC/CI além do brilho.
+#[derive(Debug, Clone)]
+pub struct MonitorOverview {
+    pub display: DisplaySummary,
+    pub resolution: (i32, i32),
+    pub resolutions: Vec<(u32, u32)>,
+    pub position: Position,
+    pub is_primary: bool,
+    pub brightness: Option<VcpValue>,
+}
+
+// Valores anteriores a uma redefinição de VCP, para desfazê-la.
+#[derive(Debug, Clone)]
+pub struct VcpBackup {
+    pub monitor: String,
+    pub index: u32,
+    // Por monitor físico, para restaurar cada um com os próprios valores.
+    pub values: Vec<(u32, Vec<(u8, u32)>)>,
+}
+
+pub enum Event {
+    Started(String),
+    Monitor(Box<MonitorState>),
+    Overview(Vec<MonitorOverview>),
+    Backup(VcpBackup),
+    Profile(Profile),
+    Layout(Layout),
+    // Monitores conectados, desconectados ou reposicionados fora do programa.
+    TopologyChanged,
+    Finished { command: Command, result: Result<()> },
+}
+
+pub struct Worker {
+    sender: Sender<Command>,
+    events: Receiver<Event>,
+}
+
+impl Worker {
+    pub fn new(notify: impl Fn() + Send + Sync + 'static) -> Self {
+        let (sender, commands) = mpsc::channel();
+        let (events_sender, events) = mpsc::channel();
+        let notify = Arc::new(notify);
+        let watch_sender = events_sender.clone();
+        let watch_notify = notify.clone();
+        thread::spawn(move || run(commands, events_sender, move || notify()));
+        thread::spawn(move || watch_topology(display_topology, TOPOLOGY_INTERVAL, watch_sender, move || watch_notify()));
+        Self { sender, events }
+    }
+
+    pub fn send(&self, command: Command) {
+        let _ = self.sender.send(command);
+    }
+
+    pub fn poll(&self) -> Vec<Event> {
+        self.events.try_iter().collect()
+    }
+}
+
+fn run(commands: Receiver<Command>, events: Sender<Event>, notify: impl Fn()) {
+    for command in commands {
+        if events.send(Event::Started(command.label())).is_err() {
+            return;
+        }
+        notify();
+
+        let result = match &command {
+            Command::Refresh { index, physical, capabilities } => {
+                read_monitor(*index, *physical, capabilities.clone())
+                    .map(|state| {
+                        let _ = events.send(Event::Monitor(Box::new(state)));
+                    })
+            }
+            Command::Overview => read_overview(&SystemDisplays).map(|overview| {
+                let _ = events.send(Event::Overview(overview));
+            }),
+            Command::SetResolution { index, width, height } => set_resolution(*index, *width, *height),
+            Command::SetPrimary { index } => set_primary_display(*index),
+            Command::MoveLeft { index } => move_to_edge(&SystemDisplays, *index, false),
+            Command::MoveRight { index } => move_to_edge(&SystemDisplays, *index, true),
+            Command::Move { index, x, y } => SystemDisplays.move_display(*index, *x, *y),
+            Command::AutoAdjust { index, physical } => auto_adjust(*index, *physical),
+            Command::Reset { index, physical, categories } => {
+                // A cópia vai para a janela antes do reset, mesmo que ele falhe no meio.
+                let _ = events.send(Event::Backup(backup_vcp(*index, *physical, categories)));
+                reset_monitor(*index, *physical, categories)
+            }
+            Command::RestoreVcp { backup } => restore_vcp(backup),
+            Command::SetVcp { index, physical, code, value } => set_vcp(*index, *physical, *code, *value),
+            Command::SetInputSource { index, physical, source } => set_input_source(*index, *physical, *source),
+            Command::SetPowerMode { index, physical, mode } => set_power_mode(*index, *physical, *mode),
+            Command::TurnOffOthers { index, mode } => turn_off_others(*index, *mode),
+            Command::ExportInventory { path, nicknames } => export_inventory(path, nicknames),
+            Command::CaptureProfile { name } => capture_profile(name).map(|profile| {
+                let _ = events.send(Event::Profile(profile));
+            }),
+            Command::ApplyProfile { profile } => apply_profile(profile),
+            Command::CaptureLayout { name } => capture_layout(name).map(|layout| {
+                let _ = events.send(Event::Layout(layout));
+            }),
+            Command::ApplyLayout { layout } => apply_layout(layout),
+        };
+
+        if events.send(Event::Finished { command, result }).is_err() {
+            return;
+        }
+        notify();
+    }
+}
+
+// Docks e KVMs conectam e desconectam monitores sem aviso à janela. A consulta é
+// barata, mas fica fora da thread da interface mesmo assim.
+fn watch_topology(topology: impl Fn() -> Topology, interval: Duration, events: Sender<Event>, notify: impl Fn()) {
+    let mut last = topology();
+    loop {
+        thread::sleep(interval);
+        let current = topology();
+        if current != last {
+            last = current;
+            if events.send(Event::TopologyChanged).is_err() {
+                return;
+            }
+            notify();
+        }
+    }
+}
+
+fn read_monitor(index: u32, physical: PhysicalTarget, capabilities: Option<Capabilities>) -> Result<MonitorState> {
+    let props = find_properties(index)?;
+    let mut state = MonitorState {
+        index,
+        physical,
+        displays: list_displays()?,
+        name: props.name,
+        is_primary: props.is_primary,
+        physical_monitors: list_physical_monitors(index).unwrap_or_default(),
+        capabilities: capabilities.or_else(|| get_capabilities(index, physical).ok()),
+        edid: get_edid(index).ok(),
+        ..Default::default()
+    };
+
+    state.brightness = get_vcp(index, physical, BRIGHTNESS).ok();
+    if state.advertises(INPUT_SOURCE) {
+        state.input_source = get_input_source(index, physical).ok();
+    }
+    state.image_controls = IMAGE_CONTROLS
+        .iter()
+        .filter(|(code, _)| state.advertises(*code))
+        .filter_map(|&(code, _)| get_vcp(index, physical, code).ok().map(|value| (code, value)))
+        .collect();
+    if state.advertises(POWER_MODE) {
+        state.power_mode = get_power_mode(index, physical).ok();
+    }
+
+    Ok(state)
+}
+
+fn read_overview(backend: &impl DisplayBackend) -> Result<Vec<MonitorOverview>> {
+    Ok(connected_displays(backend)?
+        .into_iter()
+        .map(|(display, props)| MonitorOverview {
+            resolution: (props.resolution.w, props.resolution.h),
+            resolutions: props.supported_resolutions.iter().map(|r| (r.sw, r.sh)).collect(),
+            position: props.position.into(),
+            is_primary: props.is_primary,
+            brightness: backend.brightness(display.index),
+            display,
+        })
+        .collect())
+}
+
+fn backup_vcp(index: u32, physical: PhysicalTarget, categories: &[ResetCategory]) -> VcpBackup {
+    let mut codes: Vec<u8> = Vec::new();
+    for code in categories.iter().flat_map(|c| c.affected_codes()) {
+        if !codes.contains(&code) {
+            codes.push(code);
+        }
+    }
+    VcpBackup {
+        monitor: find_properties(index).map(|p| p.name).unwrap_or_default(),
+        index,
+        values: read_vcp_values(index, physical, &codes),
+    }
+}
+
+fn restore_vcp(backup: &VcpBackup) -> Result<()> {
+    // Segue a ordem do backup: a predefinição de cor vem antes dos ganhos que ela sobrescreve.
+    let mut result = Ok(());
+    for (physical, values) in &backup.values {
+        for &(code, value) in values {
+            if let Err(e) = set_vcp(backup.index, PhysicalTarget::One(*physical), code, value) {
+                result = Err(e);
+            }
+        }
+    }
+    result
+}
+
+// Só entram monitores que ainda respondem pelo mesmo nome; se o movido sumiu, nada se move.
+fn move_to_edge(backend: &impl DisplayBackend, index: u32, right: bool) -> Result<()> {
+    let displays = connected_displays(backend)?;
+    let Some((_, current)) = displays.iter().find(|(display, _)| display.index == index) else {
+        return Err(windows::core::Error::new(HRESULT::from_win32(0x57), t!("error-monitor-index-not-found", index = index)));
+    };
+    let edges = displays.iter().map(|(_, props)| if right { props.position.right } else { props.position.left });
+
+    let x = if right {
+        edges.max().unwrap_or(0)
+    } else {
+        edges.min().unwrap_or(0) - current.resolution.w
+    };
+    backend.move_display(index, x, current.position.top)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::libs::functions::FakeDisplays;
+
+    #[test]
+    fn overview_skips_monitor_that_vanishes_after_enumeration() {
+        let backend = FakeDisplays::new(vec!["DISPLAY1", "DISPLAY2", "DISPLAY3"], vec!["DISPLAY1", "DISPLAY2"]);
+        let overview = read_overview(&backend).unwrap();
+        let names: Vec<&str> = overview.iter().map(|m| m.display.name.as_str()).collect();
+        assert_eq!(names, ["DISPLAY1", "DISPLAY2"]);
+        assert!(overview.iter().all(|m| m.brightness.is_some()));
+    }
+
+    #[test]
+    fn overview_skips_shifted_indices() {
+        let backend = FakeDisplays::new(vec!["DISPLAY1", "DISPLAY2"], vec!["DISPLAY2"]);
+        assert!(read_overview(&backend).unwrap().is_empty());
+    }
+
+    #[test]
+    fn moves_to_the_right_edge() {
+        let backend = FakeDisplays::new(vec!["DISPLAY1", "DISPLAY2"], vec!["DISPLAY1", "DISPLAY2"]);
+        move_to_edge(&backend, 0, true).unwrap();
+        assert_eq!(backend.moves(), [(0, 3840, 0)]);
+    }
+
+    #[test]
+    fn edges_ignore_a_vanished_monitor() {
+        let backend = FakeDisplays::new(vec!["DISPLAY1", "DISPLAY2", "DISPLAY3"], vec!["DISPLAY1", "DISPLAY2"]);
+        move_to_edge(&backend, 1, false).unwrap();
+        assert_eq!(backend.moves(), [(1, -1920, 0)]);
+    }
+
+    #[test]
+    fn moved_monitor_vanishes_before_enumeration_ends() {
+        // DISPLAY1 saiu: o índice 1 agora é de DISPLAY3, que não pode ser movido no lugar de DISPLAY2.
+        let backend = FakeDisplays::new(vec!["DISPLAY1", "DISPLAY2", "DISPLAY3"], vec!["DISPLAY2", "DISPLAY3"]);
+        assert!(move_to_edge(&backend, 1, true).is_err());
+        assert!(backend.moves().is_empty());
+    }
+
+    #[test]
+    fn moved_monitor_vanishes_in_the_middle_of_the_move() {
+        // As duas consultas da enumeração passam; o monitor some antes de ser movido.
+        let backend = FakeDisplays::new(vec!["DISPLAY1", "DISPLAY2"], vec!["DISPLAY1", "DISPLAY2"]).unplug_after(2, "DISPLAY2");
+        assert!(move_to_edge(&backend, 1, true).is_err());
+        assert!(backend.moves().is_empty());
+    }
+
+    #[test]
+    fn other_monitor_vanishes_in_the_middle_of_the_move() {
+        let backend = FakeDisplays::new(vec!["DISPLAY1", "DISPLAY2"], vec!["DISPLAY1", "DISPLAY2"]).unplug_after(1, "DISPLAY2");
+        move_to_edge(&backend, 0, true).unwrap();
+        assert_eq!(backend.moves(), [(0, 1920, 0)]);
+    }
+
+    #[test]
+    fn topology_change_is_reported_once() {
+        let (sender, events) = mpsc::channel();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        // O segundo monitor some na terceira consulta e não volta.
+        let topology = move || {
+            let mut topology = vec![("DISPLAY1".to_string(), (0, 0, 1920, 1080))];
+            if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                topology.push(("DISPLAY2".to_string(), (1920, 0, 3840, 1080)));
+            }
+            topology
+        };
+        thread::spawn(move || watch_topology(topology, Duration::from_millis(1), sender, || {}));
+
+        assert!(matches!(events.recv_timeout(Duration::from_secs(5)), Ok(Event::TopologyChanged)));
+        while calls.load(Ordering::SeqCst) < 10 {
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert!(events.try_recv().is_err());
+    }
+}
@@ -1,200 +1,1620 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod libs;
+use libs::cli::{self, is_cli};
 use libs::functions::{
+    DisplaySummary,
+    PhysicalMonitor,
+    PhysicalTarget,
+    configure_ddc,
     list_displays,
-    find_properties,
-    set_resolution,
-    auto_adjust,
-    move_display,
-    display_brightness,
-    reset_monitor,
-    set_primary_display,
-    verify_vcp
+};
+use libs::brightness::BrightnessController;
+use libs::edid::{
+    Edid,
+    VideoInput,
+};
+use libs::groups::{
+    BrightnessGroup,
+    CurvePoint,
+    GroupMember,
+    group_of,
+    invert_level,
+    map_level,
+};
+use libs::schedule::{
+    Anchor,
+    AnchorTime,
+    Scheduler,
+    sun_times,
+};
+use libs::settings::{
+    RefreshPolicy,
+    Settings,
+    load_settings,
+    read_settings,
+    save_settings,
+    settings_modified,
+    settings_path,
+};
+use libs::vcp::{
+    AUTO_SETUP,
+    BRIGHTNESS,
+    COLOR_PRESET,
+    IMAGE_CONTROLS,
+    INPUT_SOURCE,
+    from_percent,
+    POWER_MODE,
+    PowerMode,
+    ResetCategory,
+    VcpValue,
+    input_source_name,
+    standard_input_sources,
+    value_name,
+};
+use libs::inventory::{
+    InventoryFormat,
+    default_inventory_path,
+};
+use libs::hotkeys::{
+    Hotkey,
+    HotkeyAction,
+    HotkeyTarget,
+    Hotkeys,
+};
+use libs::i18n::{Language, set_language, t, tr};
+use libs::ipc::start_server;
+use libs::notifications::{
+    Notice,
+    Notifications,
+    Severity,
+};
+use libs::tray::{
+    background_active,
+    background_running,
+    run_tray,
+    set_start_at_login,
+    spawn_background,
+    start_at_login,
+};
+use libs::worker::{
+    Command,
+    Event,
+    MonitorOverview,
+    MonitorState,
+    VcpBackup,
+    Worker,
 };
 use eframe::egui::{self, RichText};
 use eframe::egui::IconData;
+use chrono::{Datelike, Local};
+use std::collections::{BTreeMap, VecDeque};
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+struct VcpControl {
+    code: u8,
+    label: &'static str,
+    value: VcpValue,
+    choices: Vec<u8>,
+}
+
+const SETTINGS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+// Nova resolução aguardando confirmação; sem resposta até o prazo, volta à anterior.
+struct ResolutionConfirm {
+    name: String,
+    previous: (u32, u32),
+    deadline: Instant,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Arrangement,
+    Monitors,
+    Vcp,
+    Profiles,
+    Settings,
+}
+
+impl Tab {
+    const ALL: [Tab; 5] = [Tab::Arrangement, Tab::Monitors, Tab::Vcp, Tab::Profiles, Tab::Settings];
+
+    fn label(self) -> String {
+        tr(match self {
+            Tab::Arrangement => "tab-arrangement",
+            Tab::Monitors => "tab-monitors",
+            Tab::Vcp => "tab-vcp",
+            Tab::Profiles => "tab-profiles",
+            Tab::Settings => "tab-settings",
+        })
+    }
+}
 
 struct DisplayManager {
+    tab: Tab,
     opcao_1: String,
-    opcao_2: String,
-    check: bool,
     valor_slider: f32,
+    physical: PhysicalTarget,
     monitor_index: u32,
-    resolutions: Vec<(u32, u32)>,
-    is_primary: bool,
-    vcp_supported: bool,
+    state: MonitorState,
+    overview: Vec<MonitorOverview>,
+    position_edits: BTreeMap<u32, (i32, i32)>,
+    input_sources: Vec<u8>,
+    image_controls: Vec<VcpControl>,
+    brightness: BrightnessController,
+    settings: Settings,
+    // Vazios quando o processo em segundo plano já cuida da agenda e dos atalhos.
+    scheduler: Option<Scheduler>,
+    worker: Worker,
+    pending: VecDeque<Command>,
+    status: String,
+    notifications: Notifications,
+    show_log: bool,
+    exported: Option<PathBuf>,
+    hotkeys: Option<Hotkeys>,
+    profile_name: String,
+    layout_name: String,
+    start_at_login: bool,
+    last_refresh: Instant,
+    previous_resolution: Option<(u32, (u32, u32))>,
+    confirm: Option<ResolutionConfirm>,
+    reset_dialog: Option<Vec<ResetCategory>>,
+    vcp_backup: Option<VcpBackup>,
 }
 
-impl Default for DisplayManager {
-    fn default() -> Self {
+impl DisplayManager {
+    fn new(ctx: &egui::Context, settings: Settings, warning: Option<String>) -> Self {
+        set_language(settings.language);
+        configure_ddc(settings.ddc.clone());
+        let ctx = ctx.clone();
+        let background = background_active();
         let mut app = Self {
+            tab: Tab::Monitors,
             opcao_1: String::new(),
-            opcao_2: String::new(),
-            check: false,
             valor_slider: 50.0,
+            physical: PhysicalTarget::All,
             monitor_index: 0,
-            resolutions: Vec::new(),
-            is_primary: false,
-            vcp_supported: false,
+            state: MonitorState::default(),
+            overview: Vec::new(),
+            position_edits: BTreeMap::new(),
+            input_sources: Vec::new(),
+            image_controls: Vec::new(),
+            brightness: BrightnessController::default(),
+            settings: settings.clone(),
+            scheduler: (!background).then(|| Scheduler::new(settings.schedule.clone(), settings.groups.clone())),
+            worker: Worker::new(move || ctx.request_repaint()),
+            pending: VecDeque::new(),
+            status: String::new(),
+            notifications: Notifications::default(),
+            show_log: false,
+            exported: None,
+            hotkeys: (!background).then(|| Hotkeys::new(&settings)),
+            profile_name: String::new(),
+            layout_name: String::new(),
+            start_at_login: start_at_login(),
+            last_refresh: Instant::now(),
+            previous_resolution: None,
+            confirm: None,
+            reset_dialog: None,
+            vcp_backup: None,
         };
-        app.refresh_monitor(0);
+        if let Some(warning) = warning {
+            app.notifications.error(None, warning);
+        }
+        if background {
+            app.notifications.warning(None, tr("background-services-running"));
+        } else if app.settings.ipc.enabled
+            && let Err(e) = start_server(&app.settings.ipc)
+        {
+            app.notifications.error(None, t!("error-ipc-server", reason = e.message()));
+        }
+        // O monitor inicial é escolhido quando a primeira visão geral chegar.
+        app.send(Command::Overview);
         app
     }
-}
 
-impl DisplayManager {
+    fn send(&mut self, command: Command) {
+        self.pending.push_back(command.clone());
+        self.worker.send(command);
+    }
+
+    fn busy(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
     fn refresh_monitor(&mut self, index: u32) {
+        if index != self.monitor_index {
+            self.physical = PhysicalTarget::All;
+        }
+        // As capabilities custam caro; só são lidas de novo quando o alvo muda.
+        let capabilities = if index == self.state.index && self.physical == self.state.physical {
+            self.state.capabilities.clone()
+        } else {
+            None
+        };
         self.monitor_index = index;
-        if let Ok(props) = find_properties(index) {
-            self.opcao_1 = props.name.clone();
-            self.opcao_2 = format!("{}x{}", props.resolution.w, props.resolution.h);
-            self.resolutions = props.supported_resolutions.iter().map(|r| (r.sw, r.sh)).collect();
-            self.is_primary = props.is_primary;
-            self.check = props.is_primary;
-        }
-        if let Ok((supported, brightness)) = verify_vcp(index) {
-            self.vcp_supported = supported;
-            if supported {
-                self.valor_slider = brightness as f32;
+        self.last_refresh = Instant::now();
+        self.send(Command::Refresh { index, physical: self.physical, capabilities });
+    }
+
+    fn refresh_all(&mut self) {
+        self.send(Command::Overview);
+        self.refresh_monitor(self.monitor_index);
+    }
+
+    fn handle_events(&mut self) {
+        for (index, code, e) in self.brightness.failures() {
+            let monitor = self.monitor_name(index);
+            let message = match code {
+                BRIGHTNESS => t!("error-brightness", reason = e.message()),
+                _ => t!("error-vcp-write", code = format!("0x{:02X}", code), reason = e.message()),
+            };
+            self.notifications.error(monitor, message);
+        }
+        for event in self.worker.poll() {
+            match event {
+                Event::Started(label) => self.status = label,
+                Event::Monitor(state) => self.apply_state(*state),
+                Event::Overview(overview) => self.apply_overview(overview),
+                Event::Backup(backup) => self.vcp_backup = Some(backup),
+                Event::Profile(profile) => {
+                    match self.settings.profiles.iter_mut().find(|p| p.name == profile.name) {
+                        Some(existing) => *existing = profile,
+                        None => self.settings.profiles.push(profile),
+                    }
+                    self.save_profiles();
+                }
+                // Uma visão geral já na fila vai ler a topologia nova de qualquer forma.
+                Event::TopologyChanged => {
+                    if !self.pending.iter().any(|c| matches!(c, Command::Overview)) {
+                        self.refresh_all();
+                    }
+                }
+                Event::Layout(layout) => {
+                    match self.settings.layouts.iter_mut().find(|l| l.name == layout.name) {
+                        Some(existing) => *existing = layout,
+                        None => self.settings.layouts.push(layout),
+                    }
+                    self.save();
+                }
+                Event::Finished { command, result } => {
+                    self.pending.pop_front();
+                    let previous = match &command {
+                        Command::SetResolution { .. } => self.previous_resolution.take(),
+                        _ => None,
+                    };
+                    match result {
+                        Ok(()) => {
+                            match &command {
+                                Command::ExportInventory { path, .. } => self.exported = Some(path.clone()),
+                                Command::SetResolution { .. } => self.ask_confirmation(previous),
+                                _ => {}
+                            }
+                            if let Some(done) = command.done_label() {
+                                self.notifications.success(done);
+                            }
+                        }
+                        Err(e) => {
+                            let monitor = command.index().and_then(|index| self.monitor_name(index));
+                            self.notifications.error(monitor, format!("{}: {}", command.label(), e.message()));
+                        }
+                    }
+                    if !matches!(command, Command::Refresh { .. } | Command::Overview)
+                        && self.settings.interface.refresh != RefreshPolicy::Manual
+                    {
+                        self.refresh_all();
+                    }
+                }
+            }
+        }
+    }
+
+    fn apply_overview(&mut self, overview: Vec<MonitorOverview>) {
+        let first = self.overview.is_empty();
+        let changed = !first && names(&self.overview) != names(&overview);
+        let before = std::mem::replace(&mut self.overview, overview);
+        if first {
+            let displays: Vec<DisplaySummary> = self.overview.iter().map(|m| m.display.clone()).collect();
+            if self.settings.migrate_monitor_keys(&displays) {
+                self.update_services();
+                self.save();
+            }
+            let last = self.settings.interface.last_monitor.as_deref();
+            let selected = self.overview.iter().find(|m| Some(m.display.key.as_str()) == last).or(self.overview.first());
+            if let Some(index) = selected.map(|m| m.display.index) {
+                self.refresh_monitor(index);
+            }
+            return;
+        }
+        if !changed {
+            return;
+        }
+
+        // Os índices seguem a ordem de enumeração e mudam quando um monitor sai ou entra.
+        self.position_edits.clear();
+        for gone in before.iter().filter(|m| !self.overview.iter().any(|o| o.display.name == m.display.name)) {
+            let nickname = self.settings.nicknames.get(&gone.display.key).map(String::as_str);
+            self.notifications.warning(Some(gone.display.label(nickname)), tr("warning-monitor-disconnected"));
+        }
+        let selected = self.overview.iter().find(|m| m.display.name == self.opcao_1).or(self.overview.first());
+        if let Some(index) = selected.map(|m| m.display.index) {
+            self.refresh_monitor(index);
+        }
+    }
+
+    // Releitura barata da topologia; a lista completa só é pedida ao worker se algo mudou.
+    fn ask_confirmation(&mut self, previous: Option<(u32, (u32, u32))>) {
+        let timeout = self.settings.interface.confirm_timeout;
+        if let Some((index, previous)) = previous
+            && timeout > 0
+        {
+            self.confirm = Some(ResolutionConfirm {
+                name: self.display_name(index).unwrap_or_default(),
+                previous,
+                deadline: Instant::now() + Duration::from_secs(timeout as u64),
+            });
+        }
+    }
+
+    fn confirm_ui(&mut self, ctx: &egui::Context) {
+        let Some(confirm) = &self.confirm else {
+            return;
+        };
+        let remaining = confirm.deadline.saturating_duration_since(Instant::now());
+        let mut keep = false;
+        let mut revert = remaining.is_zero();
+
+        egui::Window::new(tr("confirm-resolution"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(t!("confirm-resolution-countdown", seconds = remaining.as_secs() + 1));
+                ui.horizontal(|ui| {
+                    keep = ui.button(tr("confirm-keep")).clicked();
+                    revert |= ui.button(tr("confirm-revert")).clicked();
+                });
+            });
+
+        if keep {
+            self.confirm = None;
+        } else if revert {
+            let (width, height) = confirm.previous;
+            // O índice pode ter mudado se outro monitor saiu nesse meio tempo.
+            let index = self.overview.iter().find(|m| m.display.name == confirm.name).map(|m| m.display.index);
+            self.confirm = None;
+            if let Some(index) = index {
+                self.send(Command::SetResolution { index, width, height });
             }
         } else {
-            self.vcp_supported = false;
+            ctx.request_repaint_after(Duration::from_millis(250));
         }
     }
-}
 
-impl eframe::App for DisplayManager {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-    ctx.set_style({
-        let mut style = (*ctx.style()).clone();
-        style.text_styles.iter_mut().for_each(|(_, font_id)| {
-            font_id.size = 15.0;
-        });
-        style
-    });
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.spacing_mut().interact_size.y = 30.0;
-            egui::ComboBox::from_id_salt("monitor")
-                .width(150.0)
-                .height(30.0)
-                .selected_text(&self.opcao_1)
-                .show_ui(ui, |ui| {
-                    for monitor in list_displays().unwrap() {
-                        if ui.selectable_value(&mut self.opcao_1, monitor.name.clone(), monitor.name).clicked() {
-                            self.refresh_monitor(monitor.index);
+    // Explica o que cada categoria apaga; nada vem marcado, a escolha é do usuário.
+    fn reset_ui(&mut self, ctx: &egui::Context) {
+        if self.reset_dialog.is_none() {
+            return;
+        }
+        let monitor = self.display_label(&self.opcao_1);
+        let available: Vec<ResetCategory> = ResetCategory::ALL.into_iter().filter(|c| self.state.advertises(c.code())).collect();
+        let mut confirm = false;
+        let mut cancel = false;
+        let selected = self.reset_dialog.get_or_insert_default();
+
+        egui::Window::new(tr("reset-vcp"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.set_max_width(320.0);
+                ui.label(t!("reset-intro", monitor = monitor.as_str()));
+                ui.add_space(6.0);
+                for category in available {
+                    let mut checked = selected.contains(&category);
+                    if ui.checkbox(&mut checked, category.label()).changed() {
+                        if checked {
+                            selected.push(category);
+                        } else {
+                            selected.retain(|c| *c != category);
                         }
                     }
+                    ui.label(RichText::new(category.description()).size(11.0));
+                }
+                ui.add_space(6.0);
+                ui.label(RichText::new(tr("reset-backup-note")).size(11.0));
+                ui.horizontal(|ui| {
+                    confirm = ui.add_enabled(!selected.is_empty(), egui::Button::new(tr("reset-confirm"))).clicked();
+                    cancel = ui.button(tr("cancel")).clicked();
+                });
+            });
+
+        if confirm {
+            // Envia na ordem de ResetCategory::ALL, não na ordem dos cliques.
+            let categories = ResetCategory::ALL.into_iter().filter(|c| selected.contains(c)).collect();
+            self.reset_dialog = None;
+            self.send(Command::Reset { index: self.monitor_index, physical: self.physical, categories });
+        } else if cancel {
+            self.reset_dialog = None;
+        }
+    }
+
+    fn undo_reset(&mut self) {
+        let Some(mut backup) = self.vcp_backup.take() else {
+            return;
+        };
+        match self.overview.iter().find(|m| m.display.name == backup.monitor) {
+            Some(monitor) => {
+                backup.index = monitor.display.index;
+                self.send(Command::RestoreVcp { backup });
+            }
+            None => self.notifications.warning(Some(backup.monitor.clone()), tr("warning-monitor-disconnected")),
+        }
+    }
+
+    fn settings_ui(&mut self, ui: &mut egui::Ui) {
+        let mut language = self.settings.language;
+        let interface = &mut self.settings.interface;
+        egui::Grid::new("configuracoes").num_columns(2).show(ui, |ui| {
+            ui.label(tr("settings-font-size"));
+            ui.add(egui::DragValue::new(&mut interface.font_size).range(10.0..=24.0).speed(0.5));
+            ui.end_row();
+
+            ui.label(tr("settings-window-size"));
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut interface.window_size[0]).range(300.0..=3840.0));
+                ui.label("x");
+                ui.add(egui::DragValue::new(&mut interface.window_size[1]).range(300.0..=2160.0));
+            });
+            ui.end_row();
+
+            ui.label(tr("settings-refresh"));
+            egui::ComboBox::from_id_salt("atualizacao")
+                .width(160.0)
+                .selected_text(refresh_label(interface.refresh))
+                .show_ui(ui, |ui| {
+                    for policy in [RefreshPolicy::Manual, RefreshPolicy::AfterChanges, RefreshPolicy::Periodic] {
+                        ui.selectable_value(&mut interface.refresh, policy, refresh_label(policy));
+                    }
                 });
+            ui.end_row();
+
+            if interface.refresh == RefreshPolicy::Periodic {
+                ui.label(tr("settings-refresh-interval"));
+                ui.add(egui::DragValue::new(&mut interface.refresh_interval).range(5..=3600).suffix(" s"));
+                ui.end_row();
+            }
 
-                ui.add_space(8.0);
+            ui.label(tr("settings-confirm-timeout"));
+            ui.add(egui::DragValue::new(&mut interface.confirm_timeout).range(0..=120).suffix(" s"));
+            ui.end_row();
 
-            egui::ComboBox::from_id_salt("resolucao")
-                .width(300.0)
-                .selected_text(&self.opcao_2)
+            ui.label(tr("language"));
+            egui::ComboBox::from_id_salt("idioma")
+                .width(160.0)
+                .selected_text(language.label())
                 .show_ui(ui, |ui| {
-                    for (w, h) in self.resolutions.clone() {
-                        let label = format!("{}x{}", w, h);
-                        if ui.selectable_value(&mut self.opcao_2, label.clone(), label).clicked() {
-                            let _ = set_resolution(self.monitor_index, w, h);
-                            self.refresh_monitor(self.monitor_index);
+                    for option in Language::ALL {
+                        ui.selectable_value(&mut language, option, option.label());
+                    }
+                });
+            ui.end_row();
+        });
+        if language != self.settings.language {
+            self.settings.language = language;
+            set_language(language);
+            self.save();
+            // Os nomes das tarefas e do estado atual foram montados no idioma anterior.
+            self.refresh_monitor(self.monitor_index);
+        }
+
+        ui.label(RichText::new(settings_path().display().to_string()).size(11.0));
+        if ui.button(tr("save")).clicked() {
+            let [width, height] = self.settings.interface.window_size;
+            ui.ctx().send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::vec2(width, height)));
+            self.save();
+        }
+    }
+
+    fn apply_state(&mut self, state: MonitorState) {
+        // Resultado de uma leitura antiga, já substituída por outra seleção.
+        if state.index != self.monitor_index || state.physical != self.physical {
+            return;
+        }
+
+        self.opcao_1 = state.name.clone();
+        if let Some(brightness) = state.brightness {
+            let key = self.monitor_key(&state.name);
+            let curve = group_of(&self.settings.groups, &key)
+                .and_then(|group| group.member(&key))
+                .map(|member| member.curve.as_slice())
+                .unwrap_or_default();
+            self.valor_slider = invert_level(curve, brightness.current)
+                .unwrap_or_else(|| brightness.percent()) as f32;
+        }
+        self.input_sources = match &state.capabilities {
+            Some(caps) => caps.values(INPUT_SOURCE).map(|v| v.to_vec()).unwrap_or_default(),
+            None if state.brightness.is_some() => standard_input_sources(),
+            None => Vec::new(),
+        };
+        self.image_controls = state.image_controls
+            .iter()
+            .filter_map(|&(code, value)| {
+                let &(_, label) = IMAGE_CONTROLS.iter().find(|(c, _)| *c == code)?;
+                let choices = state.capabilities.as_ref()
+                    .and_then(|caps| caps.values(code))
+                    .map(|v| v.to_vec())
+                    .unwrap_or_default();
+                Some(VcpControl { code, label, value, choices })
+            })
+            .collect();
+        self.state = state;
+    }
+
+    fn apply_brightness(&self, name: &str, index: u32, physical: PhysicalTarget, level: u32) {
+        let Some(group) = group_of(&self.settings.groups, &self.monitor_key(name)) else {
+            self.brightness.set(index, physical, level);
+            return;
+        };
+
+        for display in &self.state.displays {
+            let Some(member) = group.member(&display.key) else {
+                continue;
+            };
+            match map_level(&member.curve, level) {
+                Some(value) => self.brightness.set_raw(display.index, PhysicalTarget::All, value),
+                None => self.brightness.set(display.index, PhysicalTarget::All, level),
+            }
+        }
+    }
+
+    fn groups_ui(&mut self, ui: &mut egui::Ui) {
+        let name = self.monitor_key(&self.opcao_1);
+        let groups = &mut self.settings.groups;
+        let current = groups.iter().position(|g| g.member(&name).is_some());
+
+        let mut selection = current;
+        let mut create = false;
+        egui::ComboBox::from_id_salt("grupo")
+            .width(200.0)
+            .selected_text(current.map(|i| groups[i].name.clone()).unwrap_or_else(|| tr("groups-none")))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut selection, None, tr("groups-none"));
+                for (i, group) in groups.iter().enumerate() {
+                    ui.selectable_value(&mut selection, Some(i), &group.name);
+                }
+                create = ui.selectable_label(false, tr("groups-new")).clicked();
+            });
+
+        if create || selection != current {
+            let member = current
+                .and_then(|i| groups[i].member(&name).cloned())
+                .unwrap_or(GroupMember { monitor: name.clone(), curve: Vec::new() });
+            for group in groups.iter_mut() {
+                group.members.retain(|m| m.monitor != name);
+            }
+            if create {
+                groups.push(BrightnessGroup {
+                    name: t!("groups-default-name", number = groups.len() + 1),
+                    members: vec![member],
+                });
+            } else if let Some(i) = selection {
+                groups[i].members.push(member);
+            }
+            groups.retain(|g| !g.members.is_empty());
+        }
+
+        if let Some(group) = groups.iter_mut().find(|g| g.member(&name).is_some()) {
+            ui.horizontal(|ui| {
+                ui.label(tr("groups-name"));
+                ui.text_edit_singleline(&mut group.name);
+            });
+            let members: Vec<String> = group.members
+                .iter()
+                .map(|m| match self.state.displays.iter().find(|d| d.key == m.monitor) {
+                    Some(display) => display.label(self.settings.nicknames.get(&display.key).map(String::as_str)),
+                    None => m.monitor.clone(),
+                })
+                .collect();
+            ui.label(RichText::new(members.join(", ")).size(11.0));
+
+            ui.label(RichText::new(tr("groups-curve")).size(11.0));
+            let max = self.state.brightness.map_or(100, |b| b.max);
+            if let Some(member) = group.member_mut(&name) {
+                let mut remove = None;
+                for (i, point) in member.curve.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(&mut point.level).range(0..=100).suffix("%"));
+                        ui.label("→");
+                        ui.add(egui::DragValue::new(&mut point.value).range(0..=max));
+                        if ui.small_button("✖").clicked() {
+                            remove = Some(i);
                         }
+                    });
+                }
+                if let Some(i) = remove {
+                    member.curve.remove(i);
+                }
+                if ui.button(tr("add-point")).clicked() {
+                    let level = member.curve.iter().map(|p| p.level).max().map_or(0, |l| (l + 25).min(100));
+                    member.curve.push(CurvePoint { level, value: from_percent(level, max) });
+                }
+            }
+        }
+
+        if ui.button(tr("save")).clicked() {
+            self.save();
+            self.update_services();
+        }
+    }
+
+    fn schedule_ui(&mut self, ui: &mut egui::Ui) {
+        let presets = self.state.capabilities.as_ref()
+            .and_then(|caps| caps.values(COLOR_PRESET))
+            .map(|v| v.to_vec())
+            .unwrap_or_default();
+        let key = self.monitor_key(&self.opcao_1);
+        let schedule = &mut self.settings.schedule;
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut schedule.enabled, tr("enable"));
+            ui.label("Lat");
+            ui.add(egui::DragValue::new(&mut schedule.latitude).range(-90.0..=90.0).speed(0.01));
+            ui.label("Lon");
+            ui.add(egui::DragValue::new(&mut schedule.longitude).range(-180.0..=180.0).speed(0.01));
+        });
+
+        let now = Local::now();
+        let sun = sun_times(now.ordinal(), schedule.latitude, schedule.longitude, now.offset().local_minus_utc() / 60);
+        let clock = |m: Option<u32>| m.map(|m| format!("{:02}:{:02}", m / 60, m % 60)).unwrap_or_else(|| "--:--".to_string());
+        ui.label(RichText::new(t!("schedule-sun-times", sunrise = clock(sun.sunrise), sunset = clock(sun.sunset))).size(11.0));
+
+        let anchors = schedule.anchors_mut(&key);
+        let mut remove = None;
+        for (i, anchor) in anchors.iter_mut().enumerate() {
+            ui.horizontal_wrapped(|ui| {
+                let label = tr(match anchor.time {
+                    AnchorTime::Fixed { .. } => "schedule-fixed",
+                    AnchorTime::Sunrise { .. } => "schedule-sunrise",
+                    AnchorTime::Sunset { .. } => "schedule-sunset",
+                });
+                egui::ComboBox::from_id_salt(("ancora", i))
+                    .width(95.0)
+                    .selected_text(label)
+                    .show_ui(ui, |ui| {
+                        if ui.selectable_label(matches!(anchor.time, AnchorTime::Fixed { .. }), tr("schedule-fixed")).clicked() {
+                            anchor.time = AnchorTime::Fixed { minute: 8 * 60 };
+                        }
+                        if ui.selectable_label(matches!(anchor.time, AnchorTime::Sunrise { .. }), tr("schedule-sunrise")).clicked() {
+                            anchor.time = AnchorTime::Sunrise { offset: 0 };
+                        }
+                        if ui.selectable_label(matches!(anchor.time, AnchorTime::Sunset { .. }), tr("schedule-sunset")).clicked() {
+                            anchor.time = AnchorTime::Sunset { offset: 0 };
+                        }
+                    });
+
+                match &mut anchor.time {
+                    AnchorTime::Fixed { minute } => {
+                        let mut hour = *minute / 60;
+                        let mut min = *minute % 60;
+                        ui.add(egui::DragValue::new(&mut hour).range(0..=23).suffix("h"));
+                        ui.add(egui::DragValue::new(&mut min).range(0..=59).suffix("m"));
+                        *minute = hour * 60 + min;
                     }
+                    AnchorTime::Sunrise { offset } | AnchorTime::Sunset { offset } => {
+                        ui.add(egui::DragValue::new(offset).range(-720..=720).suffix(" min"));
+                    }
+                }
+
+                ui.add(egui::DragValue::new(&mut anchor.brightness).range(0..=100).suffix("%"));
+
+                if !presets.is_empty() {
+                    egui::ComboBox::from_id_salt(("ancora_cor", i))
+                        .width(80.0)
+                        .selected_text(anchor.color_preset.map(|p| value_name(COLOR_PRESET, p)).unwrap_or_else(|| "—".to_string()))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut anchor.color_preset, None, "—");
+                            for &preset in &presets {
+                                ui.selectable_value(&mut anchor.color_preset, Some(preset), value_name(COLOR_PRESET, preset));
+                            }
+                        });
+                }
+
+                if ui.small_button("✖").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+        if let Some(i) = remove {
+            anchors.remove(i);
+        }
+
+        let mut save = false;
+        ui.horizontal(|ui| {
+            if ui.button(tr("add-point")).clicked() {
+                anchors.push(Anchor {
+                    time: AnchorTime::Fixed { minute: 20 * 60 },
+                    brightness: 40,
+                    color_preset: None,
                 });
+            }
+            save = ui.button(tr("save")).clicked();
+        });
+        if save {
+            self.update_services();
+            self.save();
+        }
+    }
+
+    fn hotkeys_ui(&mut self, ui: &mut egui::Ui) {
+        let hotkeys = &mut self.settings.hotkeys;
+        let target_label = |target: HotkeyTarget| tr(match target {
+            HotkeyTarget::Focused => "hotkeys-target-focused",
+            HotkeyTarget::UnderCursor => "hotkeys-target-under-cursor",
+            HotkeyTarget::All => "hotkeys-target-all",
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut hotkeys.enabled, tr("enable"));
+            ui.label(tr("hotkeys-step"));
+            ui.add(egui::DragValue::new(&mut hotkeys.step).range(1..=50).suffix("%"));
+            egui::ComboBox::from_id_salt("atalho_alvo")
+                .width(110.0)
+                .selected_text(target_label(hotkeys.target))
+                .show_ui(ui, |ui| {
+                    for target in [HotkeyTarget::Focused, HotkeyTarget::UnderCursor, HotkeyTarget::All] {
+                        ui.selectable_value(&mut hotkeys.target, target, target_label(target));
+                    }
+                });
+        });
+
+        let mut actions = vec![HotkeyAction::BrightnessUp, HotkeyAction::BrightnessDown];
+        actions.extend(self.input_sources.iter().map(|&source| HotkeyAction::InputSource { source }));
+        actions.extend(self.settings.profiles.iter().map(|p| HotkeyAction::Profile { name: p.name.clone() }));
+
+        let mut remove = None;
+        for (i, binding) in hotkeys.bindings.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut binding.keys).desired_width(120.0));
+                egui::ComboBox::from_id_salt(("atalho", i))
+                    .width(120.0)
+                    .selected_text(action_label(&binding.action))
+                    .show_ui(ui, |ui| {
+                        for action in &actions {
+                            ui.selectable_value(&mut binding.action, action.clone(), action_label(action));
+                        }
+                    });
+                if ui.small_button("✖").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+        if let Some(i) = remove {
+            hotkeys.bindings.remove(i);
+        }
+
+        let mut save = false;
+        ui.horizontal(|ui| {
+            if ui.button(tr("hotkeys-add")).clicked() {
+                hotkeys.bindings.push(Hotkey { keys: "Ctrl+Alt+".to_string(), action: HotkeyAction::BrightnessUp });
+            }
+            save = ui.button(tr("save")).clicked();
+        });
+        if save {
+            self.save();
+            self.update_services();
+        }
+
+        let failed = self.hotkeys.as_ref().map(Hotkeys::failed).unwrap_or_default();
+        if !failed.is_empty() {
+            ui.label(RichText::new(t!("hotkeys-failed", keys = failed.join(", "))).size(11.0).color(ui.visuals().error_fg_color));
+        }
+    }
+
+    fn profiles_ui(&mut self, ui: &mut egui::Ui, busy: bool) {
+        let mut apply = None;
+        let mut remove = None;
+        for (i, profile) in self.settings.profiles.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(&profile.name);
+                if ui.add_enabled(!busy, egui::Button::new(tr("profiles-apply"))).clicked() {
+                    apply = Some(profile.clone());
+                }
+                if ui.small_button("✖").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+        if let Some(profile) = apply {
+            self.send(Command::ApplyProfile { profile });
+        }
+        if let Some(i) = remove {
+            self.settings.profiles.remove(i);
+            self.save_profiles();
+        }
+
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.profile_name).desired_width(180.0).hint_text(tr("profiles-name")));
+            let name = self.profile_name.trim().to_string();
+            if ui.add_enabled(!busy && !name.is_empty(), egui::Button::new(tr("profiles-save-current"))).clicked() {
+                self.send(Command::CaptureProfile { name });
+                self.profile_name.clear();
+            }
+        });
+    }
+
+    // Tamanho da janela e último monitor são lembrados ao fechar.
+    fn save_interface(&mut self, ctx: &egui::Context) {
+        if let Some(rect) = ctx.input(|i| i.viewport().inner_rect) {
+            self.settings.interface.window_size = [rect.width(), rect.height()];
+        }
+        self.settings.interface.last_monitor = Some(self.monitor_key(&self.opcao_1)).filter(|key| !key.is_empty());
+        self.save();
+    }
+
+    fn save_profiles(&mut self) {
+        self.save();
+        self.update_services();
+    }
+
+    // Sem serviços próprios, o processo em segundo plano relê o arquivo salvo.
+    fn update_services(&self) {
+        if let Some(scheduler) = &self.scheduler {
+            scheduler.update(self.settings.schedule.clone(), self.settings.groups.clone());
+        }
+        if let Some(hotkeys) = &self.hotkeys {
+            hotkeys.update(&self.settings);
+        }
+    }
+
+    fn power_modes(&self) -> Vec<PowerMode> {
+        let advertised = self.state.capabilities.as_ref().and_then(|caps| caps.values(POWER_MODE));
+        [PowerMode::On, PowerMode::Standby, PowerMode::Off]
+            .into_iter()
+            .filter(|mode| advertised.is_none_or(|values| values.contains(&(*mode as u8))))
+            .collect()
+    }
+
+    fn display_name(&self, index: u32) -> Option<String> {
+        let display = self.overview.iter().map(|m| &m.display).chain(&self.state.displays).find(|d| d.index == index)?;
+        Some(display.name.clone())
+    }
 
-            ui.add_space(8.0);
+    fn monitor_name(&self, index: u32) -> Option<String> {
+        self.display_name(index).map(|name| self.display_label(&name))
+    }
+
+    fn save(&mut self) {
+        if let Err(e) = save_settings(&self.settings) {
+            self.notifications.error(None, t!("error-save-settings", reason = e.to_string()));
+        }
+    }
+
+    fn display_label(&self, name: &str) -> String {
+        match self.state.displays.iter().find(|d| d.name == name) {
+            Some(display) => display.label(self.settings.nicknames.get(&display.key).map(String::as_str)),
+            None => name.to_string(),
+        }
+    }
+
+    // Chave das configurações para o monitor com esse nome GDI.
+    fn monitor_key(&self, name: &str) -> String {
+        self.overview
+            .iter()
+            .map(|m| &m.display)
+            .chain(&self.state.displays)
+            .find(|d| d.name == name)
+            .map_or_else(|| name.to_string(), |d| d.key.clone())
+    }
+
+    fn nickname_ui(&mut self, ui: &mut egui::Ui) {
+        let mut save = false;
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(tr("nickname")).size(12.0));
+            let key = self.monitor_key(&self.opcao_1);
+            let nickname = self.settings.nicknames.entry(key).or_default();
+            ui.add(egui::TextEdit::singleline(nickname).desired_width(180.0));
+            save = ui.button(tr("save")).clicked();
+        });
+        if save {
+            self.settings.nicknames.retain(|_, nickname| !nickname.trim().is_empty());
+            self.save();
+        }
+    }
+
+    fn advertises(&self, code: u8) -> bool {
+        self.state.advertises(code)
+    }
+
+    fn arrangement_ui(&mut self, ui: &mut egui::Ui, busy: bool) {
+        if self.overview.is_empty() {
+            return;
+        }
+        // Área de trabalho inteira, reduzida para caber na largura disponível.
+        let left = self.overview.iter().map(|m| m.position.left).min().unwrap_or(0);
+        let top = self.overview.iter().map(|m| m.position.top).min().unwrap_or(0);
+        let right = self.overview.iter().map(|m| m.position.right).max().unwrap_or(1);
+        let bottom = self.overview.iter().map(|m| m.position.bottom).max().unwrap_or(1);
+        let width = ui.available_width();
+        let scale = (width / (right - left).max(1) as f32).min(220.0 / (bottom - top).max(1) as f32);
+        let (response, painter) = ui.allocate_painter(egui::vec2(width, (bottom - top) as f32 * scale), egui::Sense::click());
+        let origin = response.rect.min;
+
+        let mut clicked = None;
+        for monitor in &self.overview {
+            let p = &monitor.position;
+            let rect = egui::Rect::from_min_max(
+                origin + egui::vec2((p.left - left) as f32 * scale, (p.top - top) as f32 * scale),
+                origin + egui::vec2((p.right - left) as f32 * scale, (p.bottom - top) as f32 * scale),
+            ).shrink(2.0);
+            let selected = monitor.display.index == self.monitor_index;
+            let visuals = ui.visuals();
+            let fill = if selected { visuals.selection.bg_fill } else { visuals.widgets.inactive.bg_fill };
+            painter.rect(rect, 4.0, fill, visuals.widgets.noninteractive.bg_stroke, egui::StrokeKind::Inside);
+            let star = if monitor.is_primary { " ★" } else { "" };
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                format!("{}{}\n{}x{}", monitor.display.index + 1, star, monitor.resolution.0, monitor.resolution.1),
+                egui::FontId::proportional(12.0),
+                visuals.text_color(),
+            );
+            if response.clicked() && response.interact_pointer_pos().is_some_and(|pos| rect.contains(pos)) {
+                clicked = Some(monitor.display.index);
+            }
+        }
+        if let Some(index) = clicked {
+            self.refresh_monitor(index);
+        }
 
-            ui.add_enabled_ui(!self.is_primary, |ui| {
-                if ui.checkbox(&mut self.check, "Principal").clicked() && !self.is_primary {
-                    let _ = set_primary_display(self.monitor_index);
-                    self.refresh_monitor(self.monitor_index);
+        ui.add_space(8.0);
+        ui.label(self.display_label(&self.opcao_1));
+        ui.add_enabled_ui(!busy, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button(tr("move-left")).clicked() {
+                    self.send(Command::MoveLeft { index: self.monitor_index });
+                }
+                if ui.button(tr("move-right")).clicked() {
+                    self.send(Command::MoveRight { index: self.monitor_index });
+                }
+                if ui.add_enabled(!self.state.is_primary, egui::Button::new(tr("primary"))).clicked() {
+                    self.send(Command::SetPrimary { index: self.monitor_index });
                 }
             });
+        });
 
-            ui.add_space(8.0);
+        ui.add_space(10.0);
+        ui.separator();
+        ui.label(RichText::new(tr("layouts")).size(12.0));
+        self.layouts_ui(ui, busy);
+    }
 
+    // Disposições salvas também aparecem no menu da bandeja.
+    fn layouts_ui(&mut self, ui: &mut egui::Ui, busy: bool) {
+        let mut apply = None;
+        let mut remove = None;
+        for (i, layout) in self.settings.layouts.iter().enumerate() {
             ui.horizontal(|ui| {
-                if ui.button("< Mover").clicked() {
-                    if let Ok(current) = find_properties(self.monitor_index) {
-                        let displays = list_displays().unwrap();
-                        let leftmost = displays.iter()
-                            .map(|d| find_properties(d.index).unwrap().position.left)
-                            .min().unwrap_or(0);
-                        let target_x = leftmost - current.resolution.w;
-                        let _ = move_display(self.monitor_index, target_x, current.position.top);
-                        self.refresh_monitor(self.monitor_index);
+                ui.label(&layout.name);
+                if ui.add_enabled(!busy, egui::Button::new(tr("layouts-apply"))).clicked() {
+                    apply = Some(layout.clone());
+                }
+                if ui.small_button("✖").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+        if let Some(layout) = apply {
+            self.send(Command::ApplyLayout { layout });
+        }
+        if let Some(i) = remove {
+            self.settings.layouts.remove(i);
+            self.save();
+        }
+
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.layout_name).desired_width(180.0).hint_text(tr("layouts-name")));
+            let name = self.layout_name.trim().to_string();
+            if ui.add_enabled(!busy && !name.is_empty(), egui::Button::new(tr("layouts-save-current"))).clicked() {
+                self.send(Command::CaptureLayout { name });
+                self.layout_name.clear();
+            }
+        });
+    }
+
+    fn monitors_ui(&mut self, ui: &mut egui::Ui, busy: bool) {
+        ui.horizontal_wrapped(|ui| {
+            for i in 0..self.overview.len() {
+                egui::Frame::group(ui.style()).show(ui, |ui| {
+                    ui.set_width(260.0);
+                    ui.vertical(|ui| self.monitor_card(ui, i, busy));
+                });
+            }
+        });
+    }
+
+    // Cada cartão age sobre o próprio monitor, independente do selecionado.
+    fn monitor_card(&mut self, ui: &mut egui::Ui, i: usize, busy: bool) {
+        let monitor = self.overview[i].clone();
+        let index = monitor.display.index;
+        let nickname = self.settings.nicknames.get(&monitor.display.key).map(String::as_str);
+        ui.label(RichText::new(monitor.display.label(nickname)).strong());
+
+        ui.add_enabled_ui(!busy, |ui| {
+            let (width, height) = monitor.resolution;
+            egui::ComboBox::from_id_salt(("modo", index))
+                .width(240.0)
+                .selected_text(format!("{}x{}", width, height))
+                .show_ui(ui, |ui| {
+                    for (w, h) in monitor.resolutions.clone() {
+                        let current = (w as i32, h as i32) == monitor.resolution;
+                        if ui.selectable_label(current, format!("{}x{}", w, h)).clicked() && !current {
+                            self.previous_resolution = Some((index, (width as u32, height as u32)));
+                            self.send(Command::SetResolution { index, width: w, height: h });
+                        }
                     }
+                });
+
+            ui.horizontal(|ui| {
+                let current = (monitor.position.left, monitor.position.top);
+                let (mut x, mut y) = self.position_edits.get(&index).copied().unwrap_or(current);
+                ui.label(tr("position"));
+                let edited = ui.add(egui::DragValue::new(&mut x)).changed() | ui.add(egui::DragValue::new(&mut y)).changed();
+                if edited {
+                    self.position_edits.insert(index, (x, y));
+                }
+                if ui.add_enabled((x, y) != current, egui::Button::new(tr("apply"))).clicked() {
+                    self.position_edits.remove(&index);
+                    self.send(Command::Move { index, x, y });
+                }
+            });
+
+            let mut primary = monitor.is_primary;
+            if ui.add_enabled(!monitor.is_primary, egui::Checkbox::new(&mut primary, tr("primary"))).clicked() {
+                self.send(Command::SetPrimary { index });
+            }
+        });
+
+        if let Some(brightness) = monitor.brightness {
+            let mut level = brightness.percent();
+            ui.horizontal(|ui| {
+                ui.label(tr("brightness"));
+                ui.spacing_mut().slider_width = 150.0;
+                if ui.add(egui::Slider::new(&mut level, 0..=100)).changed() {
+                    self.overview[i].brightness = Some(VcpValue { current: from_percent(level, brightness.max), ..brightness });
+                    self.apply_brightness(&monitor.display.name, index, PhysicalTarget::All, level);
                 }
-                ui.add_space(220.0);
-                if ui.button("Mover >").clicked() {
-                    if let Ok(current) = find_properties(self.monitor_index) {
-                        let displays = list_displays().unwrap();
-                        let rightmost = displays.iter()
-                            .map(|d| find_properties(d.index).unwrap().position.right)
-                            .max().unwrap_or(0);
-                        let _ = move_display(self.monitor_index, rightmost, current.position.top);
-                        self.refresh_monitor(self.monitor_index);
+            });
+        }
+    }
+
+    fn vcp_ui(&mut self, ui: &mut egui::Ui, busy: bool) {
+        egui::ComboBox::from_id_salt("monitor")
+            .width(300.0)
+            .selected_text(self.display_label(&self.opcao_1))
+            .show_ui(ui, |ui| {
+                for display in self.state.displays.clone() {
+                    let label = self.display_label(&display.name);
+                    if ui.selectable_value(&mut self.opcao_1, display.name, label).clicked() {
+                        self.refresh_monitor(display.index);
                     }
                 }
             });
+        ui.label(RichText::new("Virtual Channel Processing (VCP)").size(12.0));
+        ui.add_space(8.0);
+
+        if self.state.physical_monitors.len() > 1 {
+            let label = |target: PhysicalTarget, monitors: &[PhysicalMonitor]| match target {
+                PhysicalTarget::All => tr("physical-all"),
+                PhysicalTarget::One(i) => monitors.iter()
+                    .find(|m| m.index == i)
+                    .map(|m| format!("{}: {}", i + 1, m.description))
+                    .unwrap_or_default(),
+            };
+            let mut selected = self.physical;
+            egui::ComboBox::from_id_salt("fisico")
+                .width(305.0)
+                .selected_text(label(self.physical, &self.state.physical_monitors))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut selected, PhysicalTarget::All, label(PhysicalTarget::All, &self.state.physical_monitors));
+                    for monitor in &self.state.physical_monitors {
+                        let target = PhysicalTarget::One(monitor.index);
+                        ui.selectable_value(&mut selected, target, label(target, &self.state.physical_monitors));
+                    }
+                });
+            if selected != self.physical {
+                self.physical = selected;
+                self.refresh_monitor(self.monitor_index);
+            }
 
             ui.add_space(10.0);
-            ui.separator();
-            ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
-                ui.label(RichText::new("Virtual Channel Processing (VCP)").size(12.0));
-            });
-
-                ui.add_enabled_ui(self.vcp_supported, |ui| {
-                    ui.label(RichText::new("Brilho").size(12.0));
-                    ui.scope(|ui| {
-                        ui.spacing_mut().slider_width = 305.0;
-                        if ui.add(egui::Slider::new(&mut self.valor_slider, 0.0..=100.0).min_decimals(0).max_decimals(0)).drag_stopped() {
-                            let _ = display_brightness(self.monitor_index, self.valor_slider as u32);
-                            self.refresh_monitor(self.monitor_index);
+        }
+
+        ui.add_enabled_ui(self.state.brightness.is_some() || self.state.capabilities.is_some(), |ui| {
+            if self.advertises(BRIGHTNESS) {
+                ui.label(RichText::new(tr("brightness")).size(12.0));
+                ui.scope(|ui| {
+                    ui.spacing_mut().slider_width = 305.0;
+                    let before = self.valor_slider;
+                    let response = ui.add(egui::Slider::new(&mut self.valor_slider, 0.0..=100.0).min_decimals(0).max_decimals(0));
+                    if response.changed() {
+                        if response.dragged() || group_of(&self.settings.groups, &self.monitor_key(&self.opcao_1)).is_some() {
+                            self.apply_brightness(&self.opcao_1, self.monitor_index, self.physical, self.valor_slider as u32);
+                        } else {
+                            self.brightness.fade(self.monitor_index, self.physical, before as u32, self.valor_slider as u32, Duration::from_millis(400));
                         }
-                    });
+                    }
+                });
+
+                ui.add_space(10.0);
+            }
 
-                    ui.add_space(10.0);
+            if self.advertises(INPUT_SOURCE) && !self.input_sources.is_empty() {
+                ui.label(RichText::new(tr("input")).size(12.0));
+                let selected = self.state.input_source.map(input_source_name).unwrap_or_default();
+                ui.add_enabled_ui(!busy, |ui| {
+                    egui::ComboBox::from_id_salt("entrada")
+                        .width(305.0)
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            for source in self.input_sources.clone() {
+                                if ui.selectable_value(&mut self.state.input_source, Some(source), input_source_name(source)).clicked() {
+                                    self.send(Command::SetInputSource { index: self.monitor_index, physical: self.physical, source });
+                                }
+                            }
+                        });
+                });
 
-                    ui.horizontal(|ui| {
-                        ui.add_space(ui.available_width() / 5.0);
-                        if ui.button("Ajustar Bordas").clicked() {
-                            let _ = auto_adjust(self.monitor_index);
-                            self.refresh_monitor(self.monitor_index);
+                ui.add_space(10.0);
+            }
+
+            if !self.image_controls.is_empty() {
+                egui::CollapsingHeader::new(RichText::new(tr("image-and-color")).size(12.0)).show(ui, |ui| {
+                    let mut changed = None;
+                    for control in &mut self.image_controls {
+                        ui.label(RichText::new(tr(control.label)).size(12.0));
+                        if control.choices.is_empty() {
+                            ui.scope(|ui| {
+                                ui.spacing_mut().slider_width = 290.0;
+                                // Durante o arrasto o controlador espaça as escritas e descarta as intermediárias.
+                                if ui.add(egui::Slider::new(&mut control.value.current, 0..=control.value.max)).changed() {
+                                    self.brightness.set_vcp(self.monitor_index, self.physical, control.code, control.value.current);
+                                }
+                            });
+                        } else {
+                            egui::ComboBox::from_id_salt(("vcp", control.code))
+                                .width(290.0)
+                                .selected_text(value_name(control.code, control.value.current as u8))
+                                .show_ui(ui, |ui| {
+                                    for &choice in &control.choices {
+                                        if ui.selectable_label(control.value.current == choice as u32, value_name(control.code, choice)).clicked() {
+                                            changed = Some((control.code, choice as u32));
+                                        }
+                                    }
+                                });
                         }
+                    }
+                    if let Some((code, value)) = changed {
+                        self.send(Command::SetVcp { index: self.monitor_index, physical: self.physical, code, value });
+                    }
+                });
+
+                ui.add_space(10.0);
+            }
+
+            if self.advertises(BRIGHTNESS) {
+                egui::CollapsingHeader::new(RichText::new(tr("schedule")).size(12.0)).show(ui, |ui| {
+                    self.schedule_ui(ui);
+                });
+
+                ui.add_space(10.0);
+            }
+
+            if self.advertises(BRIGHTNESS) {
+                egui::CollapsingHeader::new(RichText::new(tr("brightness-group")).size(12.0)).show(ui, |ui| {
+                    self.groups_ui(ui);
+                });
 
-                        if ui.button("Redefinir VCP").clicked() {
-                            let _ = reset_monitor(self.monitor_index);
-                            self.refresh_monitor(self.monitor_index);
+                ui.add_space(10.0);
+            }
+
+            if self.advertises(POWER_MODE) {
+                ui.label(RichText::new(tr("power")).size(12.0));
+                ui.horizontal(|ui| {
+                    for mode in self.power_modes() {
+                        let label = tr(match mode {
+                            PowerMode::On => "power-on",
+                            PowerMode::Standby => "power-standby",
+                            _ => "power-off",
+                        });
+                        if ui.add_enabled(!busy && self.state.power_mode != Some(mode), egui::Button::new(label)).clicked() {
+                            self.send(Command::SetPowerMode { index: self.monitor_index, physical: self.physical, mode });
                         }
-                    });
+                    }
+                    if ui.add_enabled(!busy, egui::Button::new(tr("power-off-others"))).clicked() {
+                        self.send(Command::TurnOffOthers { index: self.monitor_index, mode: PowerMode::Off });
+                    }
                 });
-                ui.add_space(20.0);
-                ui.with_layout(egui::Layout::bottom_up(egui::Align::RIGHT), |ui| {
-                    ui.label(egui::RichText::new("made by: bouchardet").size(11.0));
+
+                ui.add_space(10.0);
+            }
+
+            ui.horizontal(|ui| {
+                ui.add_space(ui.available_width() / 5.0);
+                if self.advertises(AUTO_SETUP) && ui.add_enabled(!busy, egui::Button::new(tr("auto-adjust"))).clicked() {
+                    self.send(Command::AutoAdjust { index: self.monitor_index, physical: self.physical });
+                }
+
+                let resettable = ResetCategory::ALL.iter().any(|c| self.advertises(c.code()));
+                if resettable && ui.add_enabled(!busy, egui::Button::new(tr("reset-vcp"))).clicked() {
+                    self.reset_dialog = Some(Vec::new());
+                }
+
+                let undo = self.vcp_backup.as_ref().is_some_and(|b| b.monitor == self.opcao_1 && b.values.iter().any(|(_, values)| !values.is_empty()));
+                if undo && ui.add_enabled(!busy, egui::Button::new(tr("reset-undo"))).clicked() {
+                    self.undo_reset();
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+        egui::CollapsingHeader::new(RichText::new(tr("details")).size(12.0)).show(ui, |ui| {
+            self.nickname_ui(ui);
+            if let Some(edid) = &self.state.edid {
+                details_ui(ui, edid);
+            }
+        });
+
+    }
+
+    fn log_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(tr("log")).size(12.0));
+            if ui.small_button(tr("log-copy")).clicked() {
+                let text: Vec<String> = self.notifications.log().iter().map(notice_line).collect();
+                ui.ctx().copy_text(text.join("\n"));
+            }
+            if ui.small_button(tr("log-clear")).clicked() {
+                self.notifications.clear_log();
+                self.show_log = false;
+            }
+        });
+        egui::ScrollArea::vertical().stick_to_bottom(true).auto_shrink(false).show(ui, |ui| {
+            for notice in self.notifications.log() {
+                ui.label(RichText::new(notice_line(notice)).size(11.0));
+            }
+        });
+    }
+
+    fn toasts_ui(&mut self, ctx: &egui::Context) {
+        let toasts = self.notifications.toasts();
+        let Some(next) = toasts.iter().map(Notice::remaining).min() else {
+            return;
+        };
+
+        let mut dismiss = None;
+        egui::Area::new(egui::Id::new("avisos"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-10.0, -40.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.set_max_width(280.0);
+                for (i, notice) in toasts.iter().enumerate() {
+                    let (icon, color) = match notice.severity {
+                        Severity::Success => ("✔", ui.visuals().text_color()),
+                        Severity::Warning => ("⚠", ui.visuals().warn_fg_color),
+                        Severity::Error => ("⚠", ui.visuals().error_fg_color),
+                    };
+                    let frame = egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        if let Some(monitor) = &notice.monitor {
+                            ui.label(RichText::new(monitor).size(11.0).strong());
+                        }
+                        let count = if notice.count > 1 { format!(" (×{})", notice.count) } else { String::new() };
+                        ui.label(RichText::new(format!("{} {}{}", icon, notice.message, count)).size(12.0).color(color));
+                    });
+                    // Clicar fecha o aviso antes do tempo.
+                    if frame.response.interact(egui::Sense::click()).clicked() {
+                        dismiss = Some(i);
+                    }
+                }
+            });
+
+        if let Some(i) = dismiss {
+            self.notifications.dismiss(i);
+        }
+        ctx.request_repaint_after(next);
+    }
+
+    fn profiles_tab_ui(&mut self, ui: &mut egui::Ui, busy: bool) {
+        ui.label(RichText::new(tr("profiles")).size(12.0));
+        self.profiles_ui(ui, busy);
+        ui.add_space(10.0);
+        ui.separator();
+        ui.label(RichText::new(tr("hotkeys")).size(12.0));
+        self.hotkeys_ui(ui);
+    }
+
+    fn settings_tab_ui(&mut self, ui: &mut egui::Ui, busy: bool) {
+        self.settings_ui(ui);
+
+        ui.add_space(10.0);
+        egui::CollapsingHeader::new(RichText::new(tr("background")).size(12.0)).show(ui, |ui| {
+            if ui.checkbox(&mut self.start_at_login, tr("background-start-at-login")).changed()
+                && let Err(e) = set_start_at_login(self.start_at_login)
+            {
+                self.start_at_login = !self.start_at_login;
+                self.notifications.error(None, t!("error-start-at-login", reason = e.message()));
+            }
+            if ui.button(tr("background-go-to-tray")).clicked() {
+                match spawn_background() {
+                    Ok(()) => ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close),
+                    Err(e) => self.notifications.error(None, t!("error-tray", reason = e.message())),
+                }
+            }
+        });
+
+        ui.add_space(10.0);
+        egui::CollapsingHeader::new(RichText::new(tr("inventory")).size(12.0)).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                for (label, format) in [("inventory-export-json", InventoryFormat::Json), ("inventory-export-csv", InventoryFormat::Csv)] {
+                    if ui.add_enabled(!busy, egui::Button::new(tr(label))).clicked() {
+                        self.send(Command::ExportInventory {
+                            path: default_inventory_path(format),
+                            nicknames: self.settings.nicknames.clone(),
+                        });
+                    }
+                }
+            });
+            if let Some(path) = &self.exported {
+                ui.label(RichText::new(t!("inventory-saved", path = path.display().to_string())).size(11.0));
+            }
+        });
+    }
+}
+
+impl eframe::App for DisplayManager {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    ctx.set_style({
+        let mut style = (*ctx.style()).clone();
+        style.text_styles.iter_mut().for_each(|(_, font_id)| {
+            font_id.size = self.settings.interface.font_size;
+        });
+        style
+    });
+        self.handle_events();
+        let busy = self.busy();
+        if self.settings.interface.refresh == RefreshPolicy::Periodic {
+            let interval = Duration::from_secs(self.settings.interface.refresh_interval.max(1) as u64);
+            if !busy && self.last_refresh.elapsed() >= interval {
+                self.refresh_all();
+            }
+            ctx.request_repaint_after(interval.saturating_sub(self.last_refresh.elapsed()));
+        }
+        self.confirm_ui(ctx);
+        self.reset_ui(ctx);
+        if ctx.input(|i| i.viewport().close_requested()) {
+            self.save_interface(ctx);
+        }
+
+        egui::TopBottomPanel::top("abas").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                for tab in Tab::ALL {
+                    ui.selectable_value(&mut self.tab, tab, tab.label());
+                }
+            });
+        });
+
+        egui::TopBottomPanel::bottom("estado").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if busy {
+                    ui.spinner();
+                    ui.label(RichText::new(format!("{}...", self.status)).size(12.0));
+                }
+                let errors = self.notifications.log().len();
+                if errors > 0 {
+                    let label = RichText::new(t!("log-toggle", count = errors)).size(12.0).color(ui.visuals().error_fg_color);
+                    if ui.selectable_label(self.show_log, label).clicked() {
+                        self.show_log = !self.show_log;
+                    }
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.label(RichText::new("made by: bouchardet").size(11.0));
                 });
             });
+        });
 
+        if self.show_log {
+            egui::TopBottomPanel::bottom("registro").resizable(true).default_height(120.0).show(ctx, |ui| {
+                self.log_ui(ui);
+            });
+        }
+        self.toasts_ui(ctx);
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.spacing_mut().interact_size.y = 30.0;
+            egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| match self.tab {
+                Tab::Arrangement => self.arrangement_ui(ui, busy),
+                Tab::Monitors => self.monitors_ui(ui, busy),
+                Tab::Vcp => self.vcp_ui(ui, busy),
+                Tab::Profiles => self.profiles_tab_ui(ui, busy),
+                Tab::Settings => self.settings_tab_ui(ui, busy),
+            });
+        });
     }
 }
 
+fn names(overview: &[MonitorOverview]) -> Vec<String> {
+    overview.iter().map(|m| m.display.name.clone()).collect()
+}
+
+fn notice_line(notice: &Notice) -> String {
+    let mut line = notice.time.format("%H:%M:%S ").to_string();
+    if let Some(monitor) = &notice.monitor {
+        line.push_str(&format!("[{}] ", monitor));
+    }
+    line.push_str(&notice.message);
+    if notice.count > 1 {
+        line.push_str(&format!(" (×{})", notice.count));
+    }
+    line
+}
+
+fn refresh_label(policy: RefreshPolicy) -> String {
+    tr(match policy {
+        RefreshPolicy::Manual => "settings-refresh-manual",
+        RefreshPolicy::AfterChanges => "settings-refresh-after-changes",
+        RefreshPolicy::Periodic => "settings-refresh-periodic",
+    })
+}
+
+fn action_label(action: &HotkeyAction) -> String {
+    match action {
+        HotkeyAction::BrightnessUp => tr("hotkeys-brightness-up"),
+        HotkeyAction::BrightnessDown => tr("hotkeys-brightness-down"),
+        HotkeyAction::InputSource { source } => t!("hotkeys-input", input = input_source_name(*source)),
+        HotkeyAction::Profile { name } => t!("hotkeys-profile", profile = name.as_str()),
+    }
+}
+
+fn details_ui(ui: &mut egui::Ui, edid: &Edid) {
+    let row = |ui: &mut egui::Ui, label: &str, value: String| {
+        ui.label(RichText::new(tr(label)).size(11.0));
+        ui.label(RichText::new(value).size(11.0));
+        ui.end_row();
+    };
+    let or_dash = |value: Option<String>| value.unwrap_or_else(|| "—".to_string());
+
+    egui::Grid::new("detalhes").num_columns(2).striped(true).show(ui, |ui| {
+        row(ui, "details-model", or_dash(edid.name.clone()));
+        row(ui, "details-manufacturer", t!("details-manufacturer-value", manufacturer = edid.manufacturer.as_str(), product = format!("{:04X}", edid.product_code)));
+        row(ui, "details-serial", or_dash(edid.serial_text.clone().or(edid.serial.map(|s| s.to_string()))));
+        row(ui, "details-manufactured", match edid.week {
+            Some(week) => t!("details-week-of-year", week = week, year = edid.year),
+            None => edid.year.to_string(),
+        });
+        row(ui, "EDID", format!(
+            "{}.{}{}{}",
+            edid.version.0,
+            edid.version.1,
+            if edid.extensions.is_empty() { String::new() } else { format!(" + {}", t!("details-extensions", count = edid.extensions.len())) },
+            if edid.checksum_valid { String::new() } else { format!(" ({})", tr("details-checksum-invalid")) },
+        ));
+        row(ui, "details-input", match edid.input {
+            VideoInput::Analog => tr("details-analog"),
+            VideoInput::Digital { bit_depth, interface } => format!(
+                "{}{}{}",
+                interface.map(str::to_string).unwrap_or_else(|| tr("details-digital")),
+                bit_depth.map(|b| format!(", {} bits", b)).unwrap_or_default(),
+                if edid.hdmi { ", HDMI" } else { "" },
+            ),
+        });
+        let size = edid.size_cm
+            .map(|(w, h)| (w as u32 * 10, h as u32 * 10))
+            .or_else(|| edid.preferred().and_then(|t| t.size_mm));
+        row(ui, "details-size", or_dash(size.map(|(w, h)| format!(
+            "{} x {} mm{}",
+            w,
+            h,
+            edid.diagonal_inches().map(|d| format!(" ({:.1}\")", d)).unwrap_or_default(),
+        ))));
+        row(ui, "details-native-mode", or_dash(edid.preferred().map(|t| format!("{} ({:.2} MHz)", t, t.pixel_clock_khz as f32 / 1000.0))));
+        if let Some(limits) = &edid.range_limits {
+            row(ui, "details-limits", format!(
+                "{}-{} Hz, {}-{} kHz{}",
+                limits.min_vertical_hz,
+                limits.max_vertical_hz,
+                limits.min_horizontal_khz,
+                limits.max_horizontal_khz,
+                limits.max_pixel_clock_mhz.map(|c| format!(", {} MHz", c)).unwrap_or_default(),
+            ));
+        }
+        row(ui, "details-gamma", or_dash(edid.gamma.map(|g| format!("{:.2}", g))));
+        let c = &edid.chromaticity;
+        row(ui, "details-chromaticity", format!(
+            "R {:.3},{:.3}  G {:.3},{:.3}\nB {:.3},{:.3}  W {:.3},{:.3}",
+            c.red.0, c.red.1, c.green.0, c.green.1, c.blue.0, c.blue.1, c.white.0, c.white.1,
+        ));
+        if !edid.colorimetry.is_empty() {
+            row(ui, "details-colorimetry", edid.colorimetry.join(", "));
+        }
+        if let Some(hdr) = &edid.hdr {
+            let nits = |v: Option<f32>| v.map(|v| format!("{:.0}", v)).unwrap_or_else(|| "?".to_string());
+            row(ui, "HDR", format!(
+                "{}\n{}",
+                hdr.eotfs.join(", "),
+                t!(
+                    "details-hdr-luminance",
+                    max = nits(hdr.max_luminance),
+                    average = nits(hdr.max_frame_average),
+                    min = hdr.min_luminance.map(|v| format!("{:.3}", v)).unwrap_or_else(|| "?".to_string()),
+                ),
+            ));
+        }
+        if !edid.native_vics.is_empty() {
+            row(ui, "details-native-vics", edid.native_vics.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", "));
+        }
+    });
+
+    egui::CollapsingHeader::new(RichText::new(tr("details-supported-modes")).size(11.0)).show(ui, |ui| {
+        for timing in edid.supported_timings() {
+            ui.label(RichText::new(timing.to_string()).size(11.0));
+        }
+    });
+}
 
 fn main() -> eframe::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    if is_cli(&args[1..]) {
+        std::process::exit(cli::run(&args[1..]));
+    }
+
+    if args.iter().any(|arg| arg == "--background") {
+        if background_running() {
+            return Ok(());
+        }
+        let mut settings = load_settings();
+        set_language(settings.language);
+        configure_ddc(settings.ddc.clone());
+        if settings.migrate_monitor_keys(&list_displays().unwrap_or_default()) {
+            let _ = save_settings(&settings);
+        }
+        if settings.ipc.enabled {
+            let _ = start_server(&settings.ipc);
+        }
+        let hotkeys = Hotkeys::new(&settings);
+        let scheduler = Scheduler::new(settings.schedule, settings.groups);
+        // A janela aberta pela bandeja não tem serviços próprios; ela só salva o arquivo.
+        std::thread::spawn(move || {
+            let mut modified = settings_modified();
+            loop {
+                std::thread::sleep(SETTINGS_POLL_INTERVAL);
+                let current = settings_modified();
+                if current != modified {
+                    modified = current;
+                    let settings = load_settings();
+                    hotkeys.update(&settings);
+                    scheduler.update(settings.schedule, settings.groups);
+                }
+            }
+        });
+        let _ = run_tray();
+        return Ok(());
+    }
+
     let icon_bytes = include_bytes!("../icon.png");
     let image = image::load_from_memory(icon_bytes).expect("Imagem inválida").to_rgba8();
     let (width, height) = image.dimensions();
     let rgba = image.into_raw();
 
     let icon = Arc::new(IconData { rgba, width, height });
+    let (settings, warning) = read_settings();
 
     let options = eframe::NativeOptions {
     viewport: egui::ViewportBuilder::default()
-        .with_inner_size([367.0, 315.0])
+        .with_inner_size(settings.interface.window_size)
         .with_title("Display Manager")
-        .with_resizable(false)
-        .with_maximize_button(false)
-        .with_minimize_button(false)
+        .with_min_inner_size([360.0, 320.0])
         .with_icon(icon),
     ..Default::default()
     };
@@ -202,6 +1622,6 @@ fn main() -> eframe::Result<()> {
     eframe::run_native(
         "Display Manager",
         options,
-        Box::new(|_| Ok(Box::new(DisplayManager::default()))),
+        Box::new(|cc| Ok(Box::new(DisplayManager::new(&cc.egui_ctx, settings, warning)))),
     )
 }